@@ -1,4 +1,5 @@
+use advent_of_code_2018::days;
+
 fn main() {
-    // 470
-    // 790
+    days::main(1);
 }
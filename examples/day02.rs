@@ -1,4 +1,5 @@
+use advent_of_code_2018::days;
+
 fn main() {
-    // 5390
-    // nvosmkcdtdbfhyxsphzgraljq
+    days::main(2);
 }
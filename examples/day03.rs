@@ -1,4 +1,5 @@
+use advent_of_code_2018::days;
+
 fn main() {
-    // 104241
-    // 806
+    days::main(3);
 }
@@ -1,4 +1,5 @@
+use advent_of_code_2018::days;
+
 fn main() {
-    // 19830
-    // 43695
+    days::main(4);
 }
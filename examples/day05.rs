@@ -1,4 +1,5 @@
+use advent_of_code_2018::days;
+
 fn main() {
-    // 9900
-    // 4992
+    days::main(5);
 }
@@ -1,4 +1,5 @@
+use advent_of_code_2018::days;
+
 fn main() {
-    // 6047
-    // 46320
+    days::main(6);
 }
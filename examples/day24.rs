@@ -0,0 +1,5 @@
+use advent_of_code_2018::days;
+
+fn main() {
+    days::main(24);
+}
@@ -0,0 +1,225 @@
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::process;
+use std::time::Duration;
+
+use advent_of_code_2018::days::answers::{Answers, Check, Status};
+use advent_of_code_2018::days::{self, Day, Logs, Part};
+use advent_of_code_2018::input::Source;
+
+const USAGE: &str = "usage:
+    aoc run <day> [--part <1|2>] [--input <path|->] [--trace <path>] [--events <path>]
+    aoc verify [<day>...]";
+
+struct Run {
+    day: &'static Day,
+    parts: Vec<Part>,
+    input: Source,
+    trace: Option<String>,
+    events: Option<String>,
+}
+
+fn parse_run(args: &[String]) -> Result<Run, String> {
+    let mut it = args.iter();
+    let number = it.next().ok_or("missing day")?;
+    let day = number
+        .parse()
+        .ok()
+        .and_then(days::find)
+        .ok_or_else(|| format!("no solution for day '{}'", number))?;
+    let mut parts = Part::ALL.to_vec();
+    let mut input = None;
+    let (mut trace, mut events) = (None, None);
+    while let Some(arg) = it.next() {
+        let mut value = || it.next().ok_or(format!("missing value of {}", arg));
+        match arg.as_str() {
+            "--part" => {
+                let part = value()?;
+                let part = part
+                    .parse()
+                    .ok()
+                    .and_then(Part::from_number)
+                    .ok_or_else(|| format!("invalid part '{}'", part))?;
+                parts = vec![part];
+            }
+            "--input" => {
+                input = Some(match value()?.as_str() {
+                    "-" => Source::Stdin,
+                    path => Source::Path(path.into()),
+                });
+            }
+            "--trace" => trace = Some(value()?.clone()),
+            "--events" => events = Some(value()?.clone()),
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+    let input = input.unwrap_or_else(|| Source::Path(day.input_path().into()));
+    Ok(Run {
+        day,
+        parts,
+        input,
+        trace,
+        events,
+    })
+}
+
+// Log files are created (or truncated) once and shared by all the parts.
+fn create(path: &Option<String>) -> Result<Option<Box<dyn Write>>, String> {
+    match path {
+        Some(path) => match File::create(path) {
+            Ok(file) => Ok(Some(Box::new(BufWriter::new(file)))),
+            Err(e) => Err(format!("{}: {}", path, e)),
+        },
+        None => Ok(None),
+    }
+}
+
+fn run(run: Run) -> Result<(), Box<dyn Error>> {
+    let text = run.input.read().map_err(|e| match &run.input {
+        Source::Path(path) => format!("{}: {}", path.display(), e),
+        _ => e.to_string(),
+    })?;
+    let mut logs = Logs {
+        trace: create(&run.trace)?,
+        events: create(&run.events)?,
+    };
+    for answer in run.day.run(&text, &run.parts, &mut logs)? {
+        println!("{}", answer);
+    }
+    Ok(())
+}
+
+// All the registered days when none are given.
+fn parse_verify(args: &[String]) -> Result<Vec<&'static Day>, String> {
+    if args.is_empty() {
+        return Ok(days::DAYS.iter().collect());
+    }
+    args.iter()
+        .map(|arg| {
+            arg.parse()
+                .ok()
+                .and_then(days::find)
+                .ok_or_else(|| format!("no solution for day '{}'", arg))
+        })
+        .collect()
+}
+
+#[derive(Default)]
+struct Summary {
+    passed: usize,
+    failed: usize,
+    missing: usize,
+    total: Duration,
+}
+
+// Answers file is optional: without it every part is reported as missing.
+fn read_answers(day: &Day) -> Result<Answers, Box<dyn Error>> {
+    match fs::read_to_string(day.answers_path()) {
+        Ok(text) => {
+            Answers::parse(&text).map_err(|e| format!("{}: {}", day.answers_path(), e).into())
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+        Err(e) => Err(format!("{}: {}", day.answers_path(), e).into()),
+    }
+}
+
+fn verify_day(day: &Day) -> Result<(Duration, Vec<Check>), Box<dyn Error>> {
+    let answers = read_answers(day)?;
+    let input =
+        fs::read_to_string(day.input_path()).map_err(|e| format!("{}: {}", day.input_path(), e))?;
+    day.verify(&input, &answers)
+}
+
+// Multi-line answers go below the label, indented.
+fn show(label: &str, answer: &str) -> String {
+    if answer.contains('\n') {
+        let lines: Vec<String> = answer.lines().map(|l| format!("        {}", l)).collect();
+        format!("    {}:\n{}", label, lines.join("\n"))
+    } else {
+        format!("    {}: {}", label, answer)
+    }
+}
+
+fn report(day: &Day, parse: Duration, checks: &[Check], summary: &mut Summary) {
+    println!("day {:02}: parse ({:.1?})", day.number, parse);
+    summary.total += parse;
+    for check in checks {
+        summary.total += check.elapsed;
+        let (status, details) = match &check.status {
+            Status::Pass => {
+                summary.passed += 1;
+                ("pass", vec![])
+            }
+            Status::Fail { expected, actual } => {
+                summary.failed += 1;
+                (
+                    "FAIL",
+                    vec![show("expected", expected), show("actual", actual)],
+                )
+            }
+            Status::Missing { actual } => {
+                summary.missing += 1;
+                ("missing", vec![show("actual", actual)])
+            }
+        };
+        println!(
+            "day {:02} part {}: {} ({:.1?})",
+            day.number, check.part, status, check.elapsed
+        );
+        for line in details {
+            println!("{}", line);
+        }
+    }
+}
+
+// Non-zero exit code if any part fails or any day can't be run at all.
+fn verify(days: Vec<&'static Day>) -> bool {
+    let mut summary = Summary::default();
+    let mut ok = true;
+    for day in days {
+        match verify_day(day) {
+            Ok((parse, checks)) => report(day, parse, &checks, &mut summary),
+            Err(e) => {
+                println!("day {:02}: ERROR {}", day.number, e);
+                ok = false;
+            }
+        }
+    }
+    println!(
+        "{} passed, {} failed, {} missing in {:.1?}",
+        summary.passed, summary.failed, summary.missing, summary.total
+    );
+    ok && summary.failed == 0
+}
+
+enum Command {
+    Run(Run),
+    Verify(Vec<&'static Day>),
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let cmd = match args.first().map(String::as_str) {
+        Some("run") => parse_run(&args[1..]).map(Command::Run),
+        Some("verify") => parse_verify(&args[1..]).map(Command::Verify),
+        _ => Err(USAGE.to_string()),
+    };
+    let cmd = cmd.unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(2);
+    });
+    match cmd {
+        Command::Run(cmd) => {
+            if let Err(e) = run(cmd) {
+                eprintln!("error: {}", e);
+                process::exit(1);
+            }
+        }
+        Command::Verify(days) => {
+            if !verify(days) {
+                process::exit(1);
+            }
+        }
+    }
+}
@@ -0,0 +1,335 @@
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use answers::{Answers, Check};
+
+pub mod answers;
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+
+// Puzzle of a single day: input is parsed once and shared by both parts.
+pub trait Solution {
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input, Box<dyn Error>>;
+    fn part1(input: &Self::Input) -> String;
+    fn part2(input: &Self::Input) -> String;
+
+    // Days that can log their steps override these, the rest ignore the logs.
+    fn part1_logged(input: &Self::Input, _logs: &mut Logs) -> io::Result<String> {
+        Ok(Self::part1(input))
+    }
+
+    fn part2_logged(input: &Self::Input, _logs: &mut Logs) -> io::Result<String> {
+        Ok(Self::part2(input))
+    }
+}
+
+// Sinks for the days that support logging, shared by all the parts that are run:
+// `trace` gets every executed instruction (19 and 21) or the map after every
+// round and the result of the search for attack power (15), `events` the combat
+// events as JSON Lines (15).
+#[derive(Default)]
+pub struct Logs {
+    pub trace: Option<Box<dyn Write>>,
+    pub events: Option<Box<dyn Write>>,
+}
+
+impl Logs {
+    // Trace borrowed for a single part.
+    pub fn trace_sink(&mut self) -> Option<&mut dyn Write> {
+        self.trace.as_mut().map(|w| w as &mut dyn Write)
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        for log in self.trace.iter_mut().chain(self.events.iter_mut()) {
+            log.flush()?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> usize {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+
+    pub fn from_number(n: usize) -> Option<Part> {
+        match n {
+            1 => Some(Part::One),
+            2 => Some(Part::Two),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+// Answer to a single part along with the time it took.
+#[derive(Debug, Clone)]
+pub struct Answer {
+    pub part: Part,
+    pub value: String,
+    pub elapsed: Duration,
+}
+
+// Answers to the requested parts, `parse` is the time spent on the input.
+#[derive(Debug, Clone)]
+pub struct Solved {
+    pub parse: Duration,
+    pub answers: Vec<Answer>,
+}
+
+type Runner = fn(&str, &[Part], &mut Logs) -> Result<Solved, Box<dyn Error>>;
+
+fn run<S: Solution>(
+    input: &str,
+    parts: &[Part],
+    logs: &mut Logs,
+) -> Result<Solved, Box<dyn Error>> {
+    let start = Instant::now();
+    let input = S::parse(input)?;
+    let parse = start.elapsed();
+    let mut answers = Vec::new();
+    for &part in parts {
+        let start = Instant::now();
+        let value = match part {
+            Part::One => S::part1_logged(&input, logs)?,
+            Part::Two => S::part2_logged(&input, logs)?,
+        };
+        answers.push(Answer {
+            part,
+            value,
+            elapsed: start.elapsed(),
+        });
+    }
+    logs.flush()?;
+    Ok(Solved { parse, answers })
+}
+
+// Registry entry with the solution type erased.
+pub struct Day {
+    pub number: usize,
+    runner: Runner,
+}
+
+impl Day {
+    // Answers to the requested parts, in the same order.
+    pub fn run(
+        &self,
+        input: &str,
+        parts: &[Part],
+        logs: &mut Logs,
+    ) -> Result<Vec<String>, Box<dyn Error>> {
+        let solved = self.solve(input, parts, logs)?;
+        Ok(solved.answers.into_iter().map(|a| a.value).collect())
+    }
+
+    pub fn solve(
+        &self,
+        input: &str,
+        parts: &[Part],
+        logs: &mut Logs,
+    ) -> Result<Solved, Box<dyn Error>> {
+        (self.runner)(input, parts, logs)
+    }
+
+    // Solves both parts and compares them to the recorded answers.
+    pub fn verify(
+        &self,
+        input: &str,
+        answers: &Answers,
+    ) -> Result<(Duration, Vec<Check>), Box<dyn Error>> {
+        let solved = self.solve(input, &Part::ALL, &mut Logs::default())?;
+        let checks = solved
+            .answers
+            .into_iter()
+            .map(|a| Check::new(a.part, a.value, answers, a.elapsed))
+            .collect();
+        Ok((solved.parse, checks))
+    }
+
+    pub fn input_path(&self) -> String {
+        format!("inputs/day{:02}.txt", self.number)
+    }
+
+    pub fn answers_path(&self) -> String {
+        format!("answers/day{:02}.txt", self.number)
+    }
+}
+
+macro_rules! day {
+    ($n:expr, $s:ty) => {
+        Day {
+            number: $n,
+            runner: run::<$s>,
+        }
+    };
+}
+
+pub const DAYS: &[Day] = &[
+    day!(1, day01::Day01),
+    day!(2, day02::Day02),
+    day!(3, day03::Day03),
+    day!(4, day04::Day04),
+    day!(5, day05::Day05),
+    day!(6, day06::Day06),
+    day!(7, day07::Day07),
+    day!(8, day08::Day08),
+    day!(9, day09::Day09),
+    day!(10, day10::Day10),
+    day!(11, day11::Day11),
+    day!(12, day12::Day12),
+    day!(13, day13::Day13),
+    day!(14, day14::Day14),
+    day!(15, day15::Day15),
+    day!(16, day16::Day16),
+    day!(17, day17::Day17),
+    day!(18, day18::Day18),
+    day!(19, day19::Day19),
+    day!(20, day20::Day20),
+    day!(21, day21::Day21),
+    day!(22, day22::Day22),
+    day!(23, day23::Day23),
+    day!(24, day24::Day24),
+    day!(25, day25::Day25),
+];
+
+pub fn find(number: usize) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
+
+// Entry point of the examples: solves both parts of the day over stdin.
+pub fn main(number: usize) {
+    let day = find(number).unwrap_or_else(|| panic!("day {} is not implemented", number));
+    match day.run(&crate::input::stdin(), &Part::ALL, &mut Logs::default()) {
+        Ok(answers) => {
+            for answer in answers {
+                println!("{}", answer);
+            }
+        }
+        Err(e) => {
+            eprintln!("invalid input: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::answers::Status;
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    // Log sink the test keeps a handle on.
+    #[derive(Clone, Default)]
+    pub(crate) struct Shared(Rc<RefCell<Vec<u8>>>);
+
+    impl Shared {
+        pub(crate) fn text(&self) -> String {
+            String::from_utf8(self.0.borrow().clone()).unwrap()
+        }
+    }
+
+    impl Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_registry() {
+        for pair in DAYS.windows(2) {
+            assert!(pair[0].number < pair[1].number);
+        }
+        assert_eq!(
+            find(15).map(|d| d.input_path()),
+            Some("inputs/day15.txt".to_string())
+        );
+        assert!(find(26).is_none());
+    }
+
+    #[test]
+    fn test_verify() {
+        let day = find(9).unwrap();
+        let answers = Answers::parse("1: 32\n2: 0").unwrap();
+        let (_, checks) = day
+            .verify("9 players; last marble is worth 25 points", &answers)
+            .unwrap();
+        let status: Vec<Status> = checks.into_iter().map(|c| c.status).collect();
+        assert_eq!(
+            status,
+            vec![
+                Status::Pass,
+                Status::Fail {
+                    expected: "0".to_string(),
+                    actual: "22563".to_string()
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_logs() {
+        let program = "#ip 0\nseti 5 0 1\nseti 6 0 2\naddi 0 1 0\naddr 1 2 3\nsetr 1 0 0\nseti 8 0 4\nseti 9 0 5";
+        let shared = Shared::default();
+        let mut logs = Logs {
+            trace: Some(Box::new(shared.clone())),
+            events: None,
+        };
+        let answers = find(19)
+            .unwrap()
+            .run(program, &Part::ALL, &mut logs)
+            .unwrap();
+        assert_eq!(answers, vec!["6", "6"]);
+        // Both parts end up in the same trace, one after the other.
+        let trace = shared.text();
+        let starts = trace.lines().filter(|l| l.starts_with("ip=0 ")).count();
+        assert_eq!(starts, 2);
+    }
+}
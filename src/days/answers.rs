@@ -0,0 +1,209 @@
+use std::error::Error;
+use std::fmt;
+use std::time::Duration;
+
+use super::Part;
+
+// Recorded answers of a day, one per part:
+//
+// 1: 470
+// 2: 790
+//
+// Multi-line answers start on the next line, every line of them prefixed with '|':
+//
+// 1:
+// |#    #  ######
+// |#   #        #
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct Answers {
+    part1: Option<String>,
+    part2: Option<String>,
+}
+
+// Line is 1-based, `found` holds the offending line.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct AnswerError {
+    pub line: usize,
+    pub expected: &'static str,
+    pub found: String,
+}
+
+impl fmt::Display for AnswerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}: expected {}, found '{}'",
+            self.line, self.expected, self.found
+        )
+    }
+}
+
+impl Error for AnswerError {}
+
+impl Answers {
+    pub fn parse(text: &str) -> Result<Answers, AnswerError> {
+        let mut answers = Answers::default();
+        let mut current: Option<Part> = None;
+        for (i, line) in crate::input::lines(text).iter().enumerate() {
+            let error = |expected| AnswerError {
+                line: i + 1,
+                expected,
+                found: line.to_string(),
+            };
+            if let Some(art) = line.strip_prefix('|') {
+                let answer = current.and_then(|part| answers.get_mut(part).as_mut());
+                let answer = answer.ok_or_else(|| error("'1:' or '2:' before '|'"))?;
+                if !answer.is_empty() {
+                    answer.push('\n');
+                }
+                answer.push_str(art);
+                continue;
+            }
+            if line.trim().is_empty() {
+                current = None;
+                continue;
+            }
+            let (part, value) = line
+                .split_once(':')
+                .and_then(|(n, value)| Some((n.trim().parse().ok()?, value.trim())))
+                .and_then(|(n, value)| Some((Part::from_number(n)?, value)))
+                .ok_or_else(|| error("'1: answer' or '2: answer'"))?;
+            if answers.get(part).is_some() {
+                return Err(error("each part only once"));
+            }
+            *answers.get_mut(part) = Some(value.to_string());
+            current = if value.is_empty() { Some(part) } else { None };
+        }
+        Ok(answers)
+    }
+
+    pub fn get(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part1.as_deref(),
+            Part::Two => self.part2.as_deref(),
+        }
+    }
+
+    fn get_mut(&mut self, part: Part) -> &mut Option<String> {
+        match part {
+            Part::One => &mut self.part1,
+            Part::Two => &mut self.part2,
+        }
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for part in Part::ALL.iter() {
+            match self.get(*part) {
+                Some(answer) if answer.contains('\n') => {
+                    writeln!(f, "{}:", part)?;
+                    for line in answer.lines() {
+                        writeln!(f, "|{}", line)?;
+                    }
+                }
+                Some(answer) => writeln!(f, "{}: {}", part, answer)?,
+                None => (),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum Status {
+    Pass,
+    Fail { expected: String, actual: String },
+    // No answer recorded: the actual one is still reported.
+    Missing { actual: String },
+}
+
+// Outcome of solving one part against the recorded answer.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Check {
+    pub part: Part,
+    pub status: Status,
+    pub elapsed: Duration,
+}
+
+impl Check {
+    pub fn new(part: Part, actual: String, answers: &Answers, elapsed: Duration) -> Check {
+        let status = match answers.get(part) {
+            Some(expected) if expected == actual => Status::Pass,
+            Some(expected) => Status::Fail {
+                expected: expected.to_string(),
+                actual,
+            },
+            None => Status::Missing { actual },
+        };
+        Check {
+            part,
+            status,
+            elapsed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let answers = Answers::parse("1: 470\n2: 790\n").unwrap();
+        assert_eq!(answers.get(Part::One), Some("470"));
+        assert_eq!(answers.get(Part::Two), Some("790"));
+        assert_eq!(answers.to_string(), "1: 470\n2: 790\n");
+
+        let text = "1:\n|#  #\n| ##\n\n2: 10932\n";
+        let answers = Answers::parse(text).unwrap();
+        assert_eq!(answers.get(Part::One), Some("#  #\n ##"));
+        assert_eq!(answers.to_string(), text.replace("\n\n", "\n"));
+
+        let answers = Answers::parse("1: 580\n").unwrap();
+        assert_eq!(answers.get(Part::Two), None);
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |line, expected: &'static str, found: &str| AnswerError {
+            line,
+            expected,
+            found: found.to_string(),
+        };
+        assert_eq!(
+            Answers::parse("1: 2\n3: 4"),
+            Err(error(2, "'1: answer' or '2: answer'", "3: 4"))
+        );
+        assert_eq!(
+            Answers::parse("1: 2\n1: 2"),
+            Err(error(2, "each part only once", "1: 2"))
+        );
+        assert_eq!(
+            Answers::parse("1: 2\n|##"),
+            Err(error(2, "'1:' or '2:' before '|'", "|##"))
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        let answers = Answers::parse("1: 470").unwrap();
+        let check = |part, actual: &str| {
+            Check::new(part, actual.to_string(), &answers, Duration::ZERO).status
+        };
+        assert_eq!(check(Part::One, "470"), Status::Pass);
+        assert_eq!(
+            check(Part::One, "471"),
+            Status::Fail {
+                expected: "470".to_string(),
+                actual: "471".to_string()
+            }
+        );
+        assert_eq!(
+            check(Part::Two, "790"),
+            Status::Missing {
+                actual: "790".to_string()
+            }
+        );
+    }
+}
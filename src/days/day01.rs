@@ -0,0 +1,82 @@
+use std::collections::HashSet;
+use std::error::Error;
+
+use super::Solution;
+
+pub struct Day01;
+
+// First cumulative frequency reached twice, the changes are repeated over and over.
+fn first_repeated(changes: &[i64]) -> Option<i64> {
+    // Each pass shifts all the frequencies by `drift`: once it moved them
+    // by more than their spread, nothing can repeat anymore.
+    let drift: i64 = changes.iter().sum();
+    let (min, max) = changes
+        .iter()
+        .scan(0, |freq, change| {
+            *freq += change;
+            Some(*freq)
+        })
+        .fold((0, 0), |(lo, hi), f| (lo.min(f), hi.max(f)));
+    let passes = if drift == 0 {
+        1
+    } else {
+        (max - min) / drift.abs() + 2
+    };
+
+    let mut seen = HashSet::new();
+    let mut freq = 0;
+    seen.insert(freq);
+    for _ in 0..passes {
+        for change in changes {
+            freq += change;
+            if !seen.insert(freq) {
+                return Some(freq);
+            }
+        }
+    }
+    None
+}
+
+impl Solution for Day01 {
+    type Input = Vec<i64>;
+
+    fn parse(input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        crate::input::lines(input)
+            .iter()
+            .enumerate()
+            .map(|(i, line)| {
+                let line = line.trim();
+                line.trim_start_matches('+')
+                    .parse()
+                    .map_err(|_| format!("line {}: invalid change '{}'", i + 1, line).into())
+            })
+            .collect()
+    }
+
+    fn part1(input: &Self::Input) -> String {
+        input.iter().sum::<i64>().to_string()
+    }
+
+    fn part2(input: &Self::Input) -> String {
+        match first_repeated(input) {
+            Some(freq) => freq.to_string(),
+            None => "none".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_repeated() {
+        assert_eq!(first_repeated(&[1, -1]), Some(0));
+        assert_eq!(first_repeated(&[3, 3, 4, -2, -4]), Some(10));
+        assert_eq!(first_repeated(&[-6, 3, 8, 5, -6]), Some(5));
+        assert_eq!(first_repeated(&[7, 7, -2, -7, -4]), Some(14));
+        assert_eq!(first_repeated(&[1, 1]), None);
+        assert_eq!(first_repeated(&[]), None);
+        assert_eq!(Day01::parse("+1\n-2\n+3\n").unwrap(), vec![1, -2, 3]);
+    }
+}
@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+use std::error::Error;
+
+use super::Solution;
+
+pub struct Day02;
+
+// Whether any letter occurs exactly twice, and exactly three times.
+fn repeats(id: &str) -> (bool, bool) {
+    let mut counts: HashMap<char, usize> = HashMap::new();
+    for c in id.chars() {
+        *counts.entry(c).or_default() += 1;
+    }
+    (
+        counts.values().any(|n| *n == 2),
+        counts.values().any(|n| *n == 3),
+    )
+}
+
+fn checksum(ids: &[String]) -> usize {
+    let (twos, threes) = ids
+        .iter()
+        .map(|id| repeats(id))
+        .fold((0, 0), |(twos, threes), (two, three)| {
+            (twos + two as usize, threes + three as usize)
+        });
+    twos * threes
+}
+
+// Common letters of the two IDs that differ by exactly one character at the same position.
+fn common(ids: &[String]) -> Option<String> {
+    for (i, a) in ids.iter().enumerate() {
+        for b in &ids[i + 1..] {
+            if a.len() != b.len() {
+                continue;
+            }
+            let same: String = a
+                .chars()
+                .zip(b.chars())
+                .filter(|(x, y)| x == y)
+                .map(|(x, _)| x)
+                .collect();
+            if same.len() + 1 == a.chars().count() {
+                return Some(same);
+            }
+        }
+    }
+    None
+}
+
+impl Solution for Day02 {
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(crate::input::lines(input)
+            .into_iter()
+            .map(|line| line.trim().to_string())
+            .collect())
+    }
+
+    fn part1(input: &Self::Input) -> String {
+        checksum(input).to_string()
+    }
+
+    fn part2(input: &Self::Input) -> String {
+        common(input).unwrap_or_else(|| "none".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wrap(lines: Vec<&'static str>) -> Vec<String> {
+        lines.into_iter().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_checksum() {
+        let ids = wrap(vec![
+            "abcdef", "bababc", "abbcde", "abcccd", "aabcdd", "abcdee", "ababab",
+        ]);
+        assert_eq!(checksum(&ids), 12);
+    }
+
+    #[test]
+    fn test_common() {
+        let ids = wrap(vec![
+            "abcde", "fghij", "klmno", "pqrst", "fguij", "axcye", "wvxyz",
+        ]);
+        assert_eq!(common(&ids), Some("fgij".to_string()));
+    }
+}
@@ -0,0 +1,105 @@
+use std::error::Error;
+
+use super::Solution;
+use crate::geom::Pos;
+use crate::grid::Grid;
+
+pub struct Day03;
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct Claim {
+    id: usize,
+    left: usize,
+    top: usize,
+    width: usize,
+    height: usize,
+}
+
+impl Claim {
+    fn cells(&self) -> impl Iterator<Item = Pos> + '_ {
+        (self.top..self.top + self.height).flat_map(move |row| {
+            (self.left..self.left + self.width).map(move |col| Pos::new(row, col))
+        })
+    }
+}
+
+// #1 @ 749,666: 27x15
+fn parse_claim(line: &str) -> Option<Claim> {
+    let (id, rest) = line.trim().strip_prefix('#')?.split_once(" @ ")?;
+    let (at, size) = rest.split_once(": ")?;
+    let (left, top) = at.split_once(',')?;
+    let (width, height) = size.split_once('x')?;
+    Some(Claim {
+        id: id.parse().ok()?,
+        left: left.parse().ok()?,
+        top: top.parse().ok()?,
+        width: width.parse().ok()?,
+        height: height.parse().ok()?,
+    })
+}
+
+// Number of claims covering each square inch.
+fn fabric(claims: &[Claim]) -> Grid<usize> {
+    let rows = claims.iter().map(|c| c.top + c.height).max().unwrap_or(0);
+    let cols = claims.iter().map(|c| c.left + c.width).max().unwrap_or(0);
+    let mut grid = Grid::new(rows, cols, 0);
+    for claim in claims {
+        for pos in claim.cells() {
+            grid[pos] += 1;
+        }
+    }
+    grid
+}
+
+impl Solution for Day03 {
+    type Input = Vec<Claim>;
+
+    fn parse(input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        crate::input::lines(input)
+            .iter()
+            .enumerate()
+            .map(|(i, line)| {
+                parse_claim(line)
+                    .ok_or_else(|| format!("line {}: invalid claim '{}'", i + 1, line).into())
+            })
+            .collect()
+    }
+
+    // Square inches within two or more claims.
+    fn part1(input: &Self::Input) -> String {
+        fabric(input).count(|n| *n > 1).to_string()
+    }
+
+    // The only claim that doesn't overlap with any other.
+    fn part2(input: &Self::Input) -> String {
+        let grid = fabric(input);
+        input
+            .iter()
+            .find(|claim| claim.cells().all(|pos| grid[pos] == 1))
+            .map(|claim| claim.id.to_string())
+            .unwrap_or_else(|| "none".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_claims() {
+        let claims = Day03::parse("#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2\n").unwrap();
+        assert_eq!(
+            claims[0],
+            Claim {
+                id: 1,
+                left: 1,
+                top: 3,
+                width: 4,
+                height: 4
+            }
+        );
+        assert_eq!(Day03::part1(&claims), "4");
+        assert_eq!(Day03::part2(&claims), "3");
+        assert!(Day03::parse("#1 @ 1,3 4x4").is_err());
+    }
+}
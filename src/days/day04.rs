@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+use std::error::Error;
+
+use super::Solution;
+
+pub struct Day04;
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+enum Event {
+    Shift(usize),
+    Sleep,
+    Wake,
+}
+
+// [1518-07-18 23:57] Guard #157 begins shift
+fn parse_record(line: &str) -> Option<(&str, usize, Event)> {
+    let (stamp, what) = line.trim().strip_prefix('[')?.split_once("] ")?;
+    let minute = stamp.rsplit(':').next()?.parse().ok()?;
+    let event = match what {
+        "falls asleep" => Event::Sleep,
+        "wakes up" => Event::Wake,
+        _ => {
+            let id = what
+                .strip_prefix("Guard #")?
+                .strip_suffix(" begins shift")?;
+            Event::Shift(id.parse().ok()?)
+        }
+    };
+    Some((stamp, minute, event))
+}
+
+// Guard -> number of times asleep at each minute of the midnight hour.
+type Schedule = HashMap<usize, [usize; 60]>;
+
+fn schedule(records: &mut [(&str, usize, Event)]) -> Result<Schedule, String> {
+    // Timestamps are zero-padded, so text order is chronological.
+    records.sort_by_key(|(stamp, _, _)| *stamp);
+    let mut result: Schedule = HashMap::new();
+    let mut guard = None;
+    let mut asleep = None;
+    for (stamp, minute, event) in records.iter() {
+        match event {
+            Event::Shift(id) => {
+                guard = Some(*id);
+                asleep = None;
+            }
+            Event::Sleep => asleep = Some(*minute),
+            Event::Wake => {
+                let id = guard.ok_or_else(|| format!("[{}] nobody is on duty", stamp))?;
+                let from =
+                    asleep.ok_or_else(|| format!("[{}] guard #{} isn't asleep", stamp, id))?;
+                let minutes = result.entry(id).or_insert([0; 60]);
+                let from = from.min(60);
+                let to = (*minute).clamp(from, 60);
+                for n in minutes[from..to].iter_mut() {
+                    *n += 1;
+                }
+                asleep = None;
+            }
+        }
+    }
+    Ok(result)
+}
+
+// Minute the guard is asleep the most, and how many times.
+fn sleepiest(minutes: &[usize; 60]) -> (usize, usize) {
+    let mut best = (0, 0);
+    for (m, n) in minutes.iter().enumerate() {
+        if *n > best.1 {
+            best = (m, *n);
+        }
+    }
+    best
+}
+
+// Guard ID times the minute, for the guard picked by `key`.
+fn strategy<F: Fn(&[usize; 60]) -> usize>(schedule: &Schedule, key: F) -> String {
+    schedule
+        .iter()
+        .max_by_key(|(id, minutes)| (key(minutes), std::cmp::Reverse(**id)))
+        .map(|(id, minutes)| (id * sleepiest(minutes).0).to_string())
+        .unwrap_or_else(|| "none".to_string())
+}
+
+impl Solution for Day04 {
+    type Input = Schedule;
+
+    fn parse(input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        let lines = crate::input::lines(input);
+        let mut records = Vec::with_capacity(lines.len());
+        for (i, line) in lines.iter().enumerate() {
+            let record = parse_record(line)
+                .ok_or_else(|| format!("line {}: invalid record '{}'", i + 1, line))?;
+            records.push(record);
+        }
+        Ok(schedule(&mut records)?)
+    }
+
+    // Guard asleep the most minutes overall.
+    fn part1(input: &Self::Input) -> String {
+        strategy(input, |minutes| minutes.iter().sum())
+    }
+
+    // Guard most frequently asleep on the same minute.
+    fn part2(input: &Self::Input) -> String {
+        strategy(input, |minutes| sleepiest(minutes).1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RECORDS: &str = "[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-01 00:05] falls asleep
+[1518-11-01 00:25] wakes up
+[1518-11-01 00:30] falls asleep
+[1518-11-01 00:55] wakes up
+[1518-11-01 23:58] Guard #99 begins shift
+[1518-11-02 00:40] falls asleep
+[1518-11-02 00:50] wakes up
+[1518-11-03 00:05] Guard #10 begins shift
+[1518-11-03 00:24] falls asleep
+[1518-11-03 00:29] wakes up
+[1518-11-04 00:02] Guard #99 begins shift
+[1518-11-04 00:36] falls asleep
+[1518-11-04 00:46] wakes up
+[1518-11-05 00:03] Guard #99 begins shift
+[1518-11-05 00:45] falls asleep
+[1518-11-05 00:55] wakes up
+";
+
+    #[test]
+    fn test_strategies() {
+        // Shuffled records must give the same answers.
+        let mut lines: Vec<&str> = RECORDS.lines().collect();
+        lines.reverse();
+        for text in [RECORDS.to_string(), lines.join("\n")].iter() {
+            let schedule = Day04::parse(text).unwrap();
+            assert_eq!(Day04::part1(&schedule), "240");
+            assert_eq!(Day04::part2(&schedule), "4455");
+        }
+        assert!(
+            Day04::parse("[1518-11-01 00:05] falls asleep\n[1518-11-01 00:25] wakes up").is_err()
+        );
+    }
+}
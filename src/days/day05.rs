@@ -0,0 +1,67 @@
+use std::error::Error;
+
+use super::Solution;
+
+pub struct Day05;
+
+// Units of the same type and opposite polarity annihilate.
+fn reacts(a: u8, b: u8) -> bool {
+    a != b && a.eq_ignore_ascii_case(&b)
+}
+
+// Length of the polymer after all the reactions, skipping units of type `without`.
+fn react(polymer: &[u8], without: Option<u8>) -> usize {
+    let mut stack: Vec<u8> = Vec::with_capacity(polymer.len());
+    for &unit in polymer {
+        if Some(unit.to_ascii_lowercase()) == without {
+            continue;
+        }
+        match stack.last() {
+            Some(&top) if reacts(top, unit) => {
+                stack.pop();
+            }
+            _ => stack.push(unit),
+        }
+    }
+    stack.len()
+}
+
+impl Solution for Day05 {
+    type Input = Vec<u8>;
+
+    fn parse(input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        let polymer = input.trim();
+        match polymer.chars().find(|c| !c.is_ascii_alphabetic()) {
+            Some(c) => Err(format!("invalid unit '{}'", c).into()),
+            None => Ok(polymer.as_bytes().to_vec()),
+        }
+    }
+
+    fn part1(input: &Self::Input) -> String {
+        react(input, None).to_string()
+    }
+
+    // Shortest polymer with all units of one type removed.
+    fn part2(input: &Self::Input) -> String {
+        (b'a'..=b'z')
+            .map(|unit| react(input, Some(unit)))
+            .min()
+            .unwrap_or(0)
+            .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_react() {
+        let polymer = Day05::parse("dabAcCaCBAcCcaDA\n").unwrap();
+        assert_eq!(Day05::part1(&polymer), "10");
+        assert_eq!(Day05::part2(&polymer), "4");
+        assert_eq!(react(b"aA", None), 0);
+        assert_eq!(react(b"abBA", None), 0);
+        assert_eq!(react(b"aabAAB", None), 6);
+    }
+}
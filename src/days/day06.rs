@@ -0,0 +1,120 @@
+use std::error::Error;
+
+use super::Solution;
+use crate::geom::Pos;
+use crate::grid::Grid;
+
+pub struct Day06;
+
+// 165, 169
+fn parse_coord(line: &str) -> Option<Pos> {
+    let (x, y) = line.split_once(',')?;
+    Some(Pos::new(y.trim().parse().ok()?, x.trim().parse().ok()?))
+}
+
+// Index of the single closest coordinate for each location within the bounding box
+// (plus a margin of one), None on ties.
+fn voronoi(coords: &[Pos]) -> Grid<Option<usize>> {
+    let rows = coords.iter().map(|p| p.row).max().unwrap_or(0) + 2;
+    let cols = coords.iter().map(|p| p.col).max().unwrap_or(0) + 2;
+    Grid::from_fn(rows, cols, |at| {
+        let mut best = None;
+        let mut min = usize::MAX;
+        for (i, p) in coords.iter().enumerate() {
+            let d = at.manhattan(*p);
+            if d < min {
+                min = d;
+                best = Some(i);
+            } else if d == min {
+                best = None;
+            }
+        }
+        best
+    })
+}
+
+// Size of the largest finite area: areas touching the border of the box go on forever.
+fn largest(coords: &[Pos]) -> usize {
+    let grid = voronoi(coords);
+    let mut areas = vec![0; coords.len()];
+    let mut infinite = vec![false; coords.len()];
+    for (at, owner) in grid.iter() {
+        if let Some(i) = *owner {
+            areas[i] += 1;
+            if at.row == 0 || at.col == 0 || at.row + 1 == grid.rows() || at.col + 1 == grid.cols()
+            {
+                infinite[i] = true;
+            }
+        }
+    }
+    areas
+        .into_iter()
+        .zip(infinite)
+        .filter(|(_, inf)| !inf)
+        .map(|(area, _)| area)
+        .max()
+        .unwrap_or(0)
+}
+
+// Locations with total distance to all coordinates below `limit`.
+fn safe(coords: &[Pos], limit: usize) -> usize {
+    if coords.is_empty() {
+        return 0;
+    }
+    // Beyond the bounding box the total grows by len() per step, so the margin
+    // of limit / len() covers the whole region.
+    let margin = limit / coords.len() + 1;
+    let rows = coords.iter().map(|p| p.row).max().unwrap_or(0) + 2 * margin + 1;
+    let cols = coords.iter().map(|p| p.col).max().unwrap_or(0) + 2 * margin + 1;
+    let shifted: Vec<Pos> = coords
+        .iter()
+        .map(|p| Pos::new(p.row + margin, p.col + margin))
+        .collect();
+    let mut count = 0;
+    for row in 0..rows {
+        for col in 0..cols {
+            let at = Pos::new(row, col);
+            let total: usize = shifted.iter().map(|p| at.manhattan(*p)).sum();
+            if total < limit {
+                count += 1;
+            }
+        }
+    }
+    count
+}
+
+impl Solution for Day06 {
+    type Input = Vec<Pos>;
+
+    fn parse(input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        crate::input::lines(input)
+            .iter()
+            .enumerate()
+            .map(|(i, line)| {
+                parse_coord(line)
+                    .ok_or_else(|| format!("line {}: invalid coordinate '{}'", i + 1, line).into())
+            })
+            .collect()
+    }
+
+    fn part1(input: &Self::Input) -> String {
+        largest(input).to_string()
+    }
+
+    fn part2(input: &Self::Input) -> String {
+        safe(input, 10000).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_areas() {
+        let coords = Day06::parse("1, 1\n1, 6\n8, 3\n3, 4\n5, 5\n8, 9\n").unwrap();
+        assert_eq!(coords[2], Pos::new(3, 8));
+        assert_eq!(largest(&coords), 17);
+        assert_eq!(safe(&coords, 32), 16);
+    }
+}
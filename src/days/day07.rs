@@ -0,0 +1,152 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::error::Error;
+
+use super::Solution;
+
+pub struct Day07;
+
+fn parse_line(line: &str) -> Option<(char, char)> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    match words.as_slice() {
+        ["Step", u, "must", "be", "finished", "before", "step", v, "can", "begin."] => {
+            Some((u.parse().ok()?, v.parse().ok()?))
+        }
+        _ => None,
+    }
+}
+
+// Steps that no other step is waiting for.
+fn roots(input: &[(char, char)]) -> Vec<char> {
+    let targets: HashSet<char> = input.iter().map(|(_, v)| *v).collect();
+    let mut roots: Vec<char> = Vec::new();
+    for (u, v) in input {
+        if !targets.contains(u) && !roots.contains(u) {
+            roots.push(*u);
+        }
+        if !targets.contains(v) && !roots.contains(v) {
+            roots.push(*v);
+        }
+    }
+    roots
+}
+
+// Priority-queue based BFS: the alphabetically first available step goes next.
+fn order(input: &[(char, char)]) -> String {
+    // node -> [nodes waiting for the key]
+    let mut blocks: BTreeMap<char, BTreeSet<char>> = BTreeMap::new();
+
+    // node -> [nodes that block the key]
+    let mut blocked: BTreeMap<char, BTreeSet<char>> = BTreeMap::new();
+
+    for (u, v) in input {
+        blocks.entry(*u).or_default().insert(*v);
+        blocked.entry(*v).or_default().insert(*u);
+    }
+
+    let mut ordered = String::new();
+    let mut queue: BTreeSet<char> = roots(input).into_iter().collect();
+    while let Some(step) = queue.iter().next().copied() {
+        queue.remove(&step);
+        if !blocked.contains_key(&step) {
+            ordered.push(step);
+
+            if let Some(nexts) = blocks.get(&step) {
+                for next in nexts {
+                    if let Some(by) = blocked.get_mut(next) {
+                        by.remove(&step);
+                        if by.is_empty() {
+                            blocked.remove(next);
+                        }
+                    }
+                    queue.insert(*next);
+                }
+            }
+        }
+    }
+    ordered
+}
+
+// Time to complete all the steps with `workers` working in parallel.
+fn assemble(input: &[(char, char)], workers: usize, base: usize) -> usize {
+    let time = |c: char| (c as u8 - b'A') as usize + 1 + base;
+
+    let mut active: HashMap<char, usize> = HashMap::new();
+    let mut clock: usize = 0;
+
+    let mut is_blocked_by: HashMap<char, HashSet<char>> = HashMap::new();
+    let mut is_blocking: HashMap<char, HashSet<char>> = HashMap::new();
+    for (u, v) in input {
+        is_blocking.entry(*u).or_default().insert(*v);
+        is_blocked_by.entry(*v).or_default().insert(*u);
+    }
+
+    let mut ready: BTreeSet<char> = roots(input).into_iter().collect();
+    while !ready.is_empty() || !active.is_empty() {
+        let mut done = HashSet::new();
+        for (c, t) in &active {
+            if clock >= *t {
+                done.insert(*c);
+
+                if let Some(nexts) = is_blocking.get(c) {
+                    for next in nexts {
+                        if let Some(by) = is_blocked_by.get_mut(next) {
+                            by.remove(c);
+                            if by.is_empty() {
+                                is_blocked_by.remove(next);
+                                ready.insert(*next);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        for d in done {
+            active.remove(&d);
+        }
+
+        let mut start: Vec<char> = Vec::new();
+        for r in &ready {
+            if is_blocked_by.contains_key(r) {
+                continue;
+            }
+            if active.len() + start.len() < workers {
+                start.push(*r);
+            }
+        }
+        for s in start {
+            active.insert(s, clock + time(s));
+            ready.remove(&s);
+        }
+
+        clock += 1;
+    }
+
+    clock - 1
+}
+
+impl Solution for Day07 {
+    type Input = Vec<(char, char)>;
+
+    fn parse(input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        input
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .enumerate()
+            .map(|(i, line)| {
+                parse_line(line)
+                    .ok_or_else(|| format!("line {}: invalid step '{}'", i + 1, line).into())
+            })
+            .collect()
+    }
+
+    fn part1(input: &Self::Input) -> String {
+        order(input)
+    }
+
+    fn part2(input: &Self::Input) -> String {
+        assemble(input, 5, 60).to_string()
+    }
+}
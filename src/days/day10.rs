@@ -0,0 +1,153 @@
+use std::error::Error;
+
+use super::Solution;
+use crate::geom::{Pos, V2};
+use crate::grid::Grid;
+
+pub struct Day10;
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct Star {
+    pos: V2,
+    vel: V2,
+}
+
+#[derive(Debug)]
+struct Size {
+    min: V2,
+    max: V2,
+}
+
+impl Size {
+    fn square(&self) -> usize {
+        (self.max.x - self.min.x) as usize * (self.max.y - self.min.y) as usize
+    }
+
+    fn width(&self) -> usize {
+        (self.max.x - self.min.x) as usize
+    }
+
+    fn height(&self) -> usize {
+        (self.max.y - self.min.y) as usize
+    }
+}
+
+// position=< 43869, -10792> velocity=<-4,  1>
+fn parse_line(line: &str) -> Option<Star> {
+    fn pair(s: &str) -> Option<V2> {
+        let s = s.split('>').next()?;
+        let mut it = s.split(',');
+        let x = it.next()?.trim().parse().ok()?;
+        let y = it.next()?.trim().parse().ok()?;
+        Some(V2::new(x, y))
+    }
+    let mut it = line.split('<').skip(1);
+    let pos = pair(it.next()?)?;
+    let vel = pair(it.next()?)?;
+    Some(Star { pos, vel })
+}
+
+fn move_stars(stars: &[Star], seconds: isize) -> (Vec<V2>, Size) {
+    let (mut xmin, mut xmax, mut ymin, mut ymax) = (i64::MAX, i64::MIN, i64::MAX, i64::MIN);
+    let mut result = Vec::new();
+    for star in stars {
+        let at = star.pos + star.vel * seconds as i64;
+        let (x, y) = (at.x, at.y);
+        result.push(at);
+
+        if x > xmax {
+            xmax = x;
+        }
+        if x < xmin {
+            xmin = x;
+        }
+        if y > ymax {
+            ymax = y;
+        }
+        if y < ymin {
+            ymin = y;
+        }
+    }
+    (
+        result,
+        Size {
+            min: V2::new(xmin, ymin),
+            max: V2::new(xmax, ymax),
+        },
+    )
+}
+
+// Stars converge while their bounding box shrinks: the message shows up at the smallest one.
+fn converge(stars: &[Star]) -> (isize, Vec<V2>, Size) {
+    let (mut dots, mut size) = move_stars(stars, 0);
+    let mut current: usize = size.square();
+    let mut seconds: isize = 0;
+    loop {
+        let (ds, sz) = move_stars(stars, seconds + 1);
+        let square = sz.square();
+        if square < current {
+            current = square;
+            seconds += 1;
+            dots = ds;
+            size = sz;
+        } else {
+            break;
+        }
+    }
+    (seconds, dots, size)
+}
+
+impl Solution for Day10 {
+    type Input = Vec<Star>;
+
+    fn parse(input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        let lines = crate::input::lines(input);
+        let mut stars = Vec::with_capacity(lines.len());
+        for (i, line) in lines.iter().enumerate() {
+            let star = parse_line(line)
+                .ok_or_else(|| format!("line {}: expected a star, found '{}'", i + 1, line))?;
+            stars.push(star);
+        }
+        if stars.is_empty() {
+            return Err("no stars in the sky".into());
+        }
+        Ok(stars)
+    }
+
+    // The message itself, as ASCII art.
+    fn part1(input: &Self::Input) -> String {
+        let (_, dots, size) = converge(input);
+        let mut matrix = Grid::new(size.height() + 1, size.width() + 1, ' ');
+        for dot in dots {
+            let at = dot - size.min;
+            matrix[Pos::new(at.y as usize, at.x as usize)] = '#';
+        }
+        let mut lines = matrix.dump();
+        for line in lines.iter_mut() {
+            line.truncate(line.trim_end().len());
+        }
+        lines.join("\n")
+    }
+
+    fn part2(input: &Self::Input) -> String {
+        let (seconds, _, _) = converge(input);
+        seconds.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_line() {
+        assert_eq!(
+            parse_line("position=< 43869, -10792> velocity=<-4,  1>"),
+            Some(Star {
+                pos: V2::new(43869, -10792),
+                vel: V2::new(-4, 1)
+            })
+        );
+        assert_eq!(parse_line("position=<1, 2>"), None);
+    }
+}
@@ -0,0 +1,1952 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::io::{self, Write};
+
+use super::{Logs, Solution};
+use crate::geom::Pos;
+use crate::grid;
+
+pub struct Day15;
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+struct Unit {
+    kind: char,
+    attack: usize,
+    health: usize,
+}
+
+impl Unit {
+    fn attack(&self, that: Unit) -> Option<Unit> {
+        if that.health <= self.attack {
+            None
+        } else {
+            Some(Unit {
+                health: that.health - self.attack,
+                ..that
+            })
+        }
+    }
+}
+
+// Anything after the map on a line (e.g. HP annotations) is ignored.
+fn map_of(lines: &[String]) -> String {
+    let map: Vec<&str> = lines
+        .iter()
+        .map(|l| l.split_whitespace().next().unwrap_or(""))
+        .collect();
+    map.join("\n")
+}
+
+// Health and attack power of the units of a faction.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+struct Stats {
+    health: usize,
+    attack: usize,
+}
+
+const DEFAULT_STATS: Stats = Stats {
+    health: 200,
+    attack: 3,
+};
+
+// Factions are the letters on the map, each one hostile to any other it isn't allied with.
+#[derive(Eq, PartialEq, Debug, Clone, Default)]
+pub struct Rules {
+    stats: HashMap<char, Stats>,
+    allies: HashSet<(char, char)>,
+    // Highest attack power of the elves tried in part 2.
+    cap: Option<usize>,
+}
+
+impl Rules {
+    fn stats(&self, kind: char) -> Stats {
+        self.stats.get(&kind).copied().unwrap_or(DEFAULT_STATS)
+    }
+
+    fn hostile(&self, a: char, b: char) -> bool {
+        a != b && !self.allies.contains(&(a, b))
+    }
+
+    fn ally(&mut self, a: char, b: char) {
+        self.allies.insert((a, b));
+        self.allies.insert((b, a));
+    }
+
+    // Header lines, each one either the stats of a faction, a group of allies
+    // or the cap on the attack power of the elves:
+    //   E: hp 300, attack 5
+    //   allies: E, D
+    //   cap: 50
+    fn parse(lines: &[String]) -> Result<Rules, String> {
+        fn faction(s: &str) -> Result<char, String> {
+            let mut chars = s.trim().chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) if c.is_ascii_alphabetic() => Ok(c),
+                _ => Err(format!("invalid faction '{}'", s.trim())),
+            }
+        }
+
+        let mut rules = Rules::default();
+        for line in lines.iter().filter(|l| !l.trim().is_empty()) {
+            let (key, value) = line.split_once(':').ok_or_else(|| {
+                format!(
+                    "expected '<faction>: <stats>', 'allies: <factions>' or 'cap: <attack>', found '{}'",
+                    line
+                )
+            })?;
+            if key.trim() == "cap" {
+                let cap = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("invalid cap: '{}'", value.trim()))?;
+                rules.cap = Some(cap);
+                continue;
+            }
+            if key.trim() == "allies" {
+                let group = value
+                    .split(',')
+                    .map(faction)
+                    .collect::<Result<Vec<char>, _>>()?;
+                for a in group.iter() {
+                    for b in group.iter() {
+                        rules.ally(*a, *b);
+                    }
+                }
+                continue;
+            }
+            let kind = faction(key)?;
+            let mut stats = rules.stats(kind);
+            for stat in value.split(',') {
+                let (name, n) = stat
+                    .trim()
+                    .split_once(' ')
+                    .ok_or_else(|| format!("expected '<stat> <value>', found '{}'", stat.trim()))?;
+                let n = n
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("invalid {} of {}: '{}'", name, kind, n.trim()))?;
+                match name {
+                    "hp" => stats.health = n,
+                    // Nobody could ever die, the combat would never end.
+                    "attack" if n == 0 => {
+                        return Err(format!("attack of {} must be positive", kind))
+                    }
+                    "attack" => stats.attack = n,
+                    _ => return Err(format!("unknown stat '{}'", name)),
+                }
+            }
+            rules.stats.insert(kind, stats);
+        }
+        Ok(rules)
+    }
+}
+
+// Combat map along with the rules given in its header (the lines above the map).
+pub struct Scenario {
+    rules: Rules,
+    map: Vec<String>,
+}
+
+impl Scenario {
+    fn parse(input: &str) -> Result<Scenario, Box<dyn Error>> {
+        let lines = crate::input::lines(input);
+        let top = lines
+            .iter()
+            .position(|l| l.starts_with('#'))
+            .ok_or("no map found")?;
+        let rules = Rules::parse(&lines[..top])?;
+        let map = lines[top..].to_vec();
+        let cells = grid::Grid::parse(&map_of(&map), |c| c)?;
+        if let Some((p, c)) = cells
+            .iter()
+            .find(|(_, c)| !(**c == '#' || **c == '.' || c.is_ascii_alphabetic()))
+        {
+            return Err(format!("unexpected '{}' at {}", c, p).into());
+        }
+        Ok(Scenario { rules, map })
+    }
+
+    fn grid(&self) -> Grid {
+        let rules = &self.rules;
+        let mut grid = Grid::parse(self.map.clone(), |chr, _row, _col| {
+            if chr.is_ascii_alphabetic() {
+                let stats = rules.stats(chr);
+                Some(Unit {
+                    kind: chr,
+                    health: stats.health,
+                    attack: stats.attack,
+                })
+            } else {
+                None
+            }
+        });
+        grid.rules = self.rules.clone();
+        grid
+    }
+
+    // Same scenario with the attack power of a faction changed.
+    fn with_attack(&self, kind: char, attack: usize) -> Scenario {
+        let mut rules = self.rules.clone();
+        let stats = rules.stats(kind);
+        rules.stats.insert(kind, Stats { attack, ..stats });
+        Scenario {
+            rules,
+            map: self.map.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+struct Grid {
+    cells: grid::Grid<char>,
+    units: grid::Grid<Option<Unit>>,
+    rules: Rules,
+    // Kind of units that must all survive: the combat stops at the first death among them.
+    guard: Option<char>,
+    scratch: RefCell<Scratch>,
+    // Buffers of `round`: the units in turn order and the cells they moved to.
+    order: Vec<Pos>,
+    moved: Vec<bool>,
+}
+
+// Buffers of the pathfinding, indexed like the cells and reused from one step to the next.
+#[derive(Clone, Default)]
+struct Scratch {
+    dist: Vec<usize>,
+    // The first in reading order among the squares in range closest to the cell.
+    near: Vec<usize>,
+    queue: Vec<usize>,
+}
+
+// Orthogonal neighbours of the cell at `i`, in reading order.
+fn around(i: usize, cols: usize, len: usize) -> impl Iterator<Item = usize> {
+    let col = i % cols;
+    let up = i.checked_sub(cols);
+    let left = if col > 0 { Some(i - 1) } else { None };
+    let right = if col + 1 < cols { Some(i + 1) } else { None };
+    let down = if i + cols < len { Some(i + cols) } else { None };
+    IntoIterator::into_iter([up, left, right, down]).flatten()
+}
+
+impl Grid {
+    fn parse<F>(lines: Vec<String>, f: F) -> Grid
+    where
+        F: Fn(char, usize, usize) -> Option<Unit>,
+    {
+        let mut cells = grid::Grid::parse(&map_of(&lines), |c| c).unwrap();
+        let units = grid::Grid::from_fn(cells.rows(), cells.cols(), |p| f(cells[p], p.row, p.col));
+        for (p, unit) in units.iter() {
+            if unit.is_some() {
+                cells[p] = '.';
+            }
+        }
+        Grid {
+            cells,
+            units,
+            rules: Rules::default(),
+            guard: None,
+            scratch: RefCell::new(Scratch::default()),
+            order: Vec::new(),
+            moved: Vec::new(),
+        }
+    }
+
+    // Map in the puzzle's format: the units of each row listed after it with their health.
+    fn dump(&self) -> Vec<String> {
+        let mut rows = self.units.render(|u| u.map(|u| u.kind).unwrap_or('.'));
+        for (p, cell) in self.cells.iter() {
+            if *cell == '#' {
+                rows[p.row].replace_range(p.col..p.col + 1, "#");
+            }
+        }
+        let mut health: Vec<Vec<String>> = vec![Vec::new(); rows.len()];
+        for (p, unit) in self.units.iter() {
+            if let Some(u) = unit {
+                health[p.row].push(format!("{}({})", u.kind, u.health));
+            }
+        }
+        for (row, units) in rows.iter_mut().zip(health) {
+            if !units.is_empty() {
+                row.push_str("   ");
+                row.push_str(&units.join(", "));
+            }
+        }
+        rows
+    }
+
+    fn count(&self, kind: char) -> usize {
+        self.units.count(|opt| opt.map(|u| u.kind) == Some(kind))
+    }
+
+    // Whether the unit at `at` is hostile to units of kind `kind`.
+    fn enemy(&self, at: Pos, kind: char) -> bool {
+        self.units[at]
+            .map(|u| self.rules.hostile(kind, u.kind))
+            .unwrap_or(false)
+    }
+
+    fn has_enemies(&self, kind: char) -> bool {
+        self.units
+            .iter()
+            .any(|(_, u)| u.map(|u| self.rules.hostile(kind, u.kind)) == Some(true))
+    }
+
+    // Adjacent enemy with the fewest hit points, the first in reading order among those.
+    fn adjacent(&self, pos: Pos) -> Option<Pos> {
+        let kind = self.units[pos]?.kind;
+        self.cells
+            .neighbours4(pos)
+            .filter(|at| self.enemy(*at, kind))
+            .min_by_key(|at| self.units[*at].map(|u| u.health))
+    }
+
+    // First move of the unit at `pos` on the way to the closest enemy: through the closest
+    // square in range of an enemy (the first in reading order among equally close ones),
+    // taking the first step in reading order among the shortest ways to that square.
+    // A single BFS from all the squares in range of the enemies at once: each cell gets
+    // its distance to the closest of them and the first in reading order among those,
+    // then the unit steps to the neighbour closest to the first closest square.
+    fn step(&self, pos: Pos) -> Option<Pos> {
+        let kind = self.units[pos]?.kind;
+        let cols = self.cells.cols();
+        let cells = self.cells.as_slice();
+        let units = self.units.as_slice();
+        let len = cells.len();
+        let start = self.cells.index_of(pos);
+        let free = |i: usize| cells[i] != '#' && units[i].is_none();
+        let hostile = |i: usize| units[i].map(|u| self.rules.hostile(kind, u.kind)) == Some(true);
+        let next_to_start = |i: usize| around(i, cols, len).any(|j| j == start);
+
+        let mut scratch = self.scratch.borrow_mut();
+        let Scratch { dist, near, queue } = &mut *scratch;
+        dist.clear();
+        dist.resize(len, usize::MAX);
+        near.clear();
+        near.resize(len, usize::MAX);
+        queue.clear();
+
+        // Distance to the closest neighbour of the unit found so far.
+        let mut reach = usize::MAX;
+        for i in 0..len {
+            if free(i) && around(i, cols, len).any(hostile) {
+                dist[i] = 0;
+                near[i] = i;
+                queue.push(i);
+                if next_to_start(i) {
+                    reach = 0;
+                }
+            }
+        }
+        let mut head = 0;
+        while head < queue.len() {
+            let i = queue[head];
+            head += 1;
+            // The cells at distance `reach` are all found and settled.
+            if dist[i] >= reach {
+                break;
+            }
+            for j in around(i, cols, len) {
+                if !free(j) {
+                    continue;
+                }
+                if dist[j] == usize::MAX {
+                    dist[j] = dist[i] + 1;
+                    near[j] = near[i];
+                    queue.push(j);
+                    if next_to_start(j) {
+                        reach = reach.min(dist[j]);
+                    }
+                } else if dist[j] == dist[i] + 1 && near[i] < near[j] {
+                    near[j] = near[i];
+                }
+            }
+        }
+
+        around(start, cols, len)
+            .filter(|j| dist[*j] != usize::MAX)
+            .min_by_key(|j| (dist[*j], near[*j], *j))
+            .map(|j| self.cells.pos_at(j))
+    }
+
+    // Adjacent enemy to attack, or else the first step towards the closest one.
+    fn target(&self, pos: Pos) -> Option<Pos> {
+        self.adjacent(pos).or_else(|| self.step(pos))
+    }
+}
+
+// What happens during the combat, in order.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+enum Event {
+    // Round `number` (1-based) begins.
+    Round {
+        number: usize,
+    },
+    Move {
+        unit: char,
+        from: Pos,
+        to: Pos,
+    },
+    // `health` is what the target has left after the hit.
+    Attack {
+        unit: char,
+        at: Pos,
+        target: Pos,
+        damage: usize,
+        health: usize,
+    },
+    Death {
+        unit: char,
+        at: Pos,
+    },
+    End {
+        rounds: usize,
+        health: usize,
+        outcome: usize,
+    },
+}
+
+impl Event {
+    // One line of JSON, positions as `[row, col]`.
+    fn json(&self) -> String {
+        match *self {
+            Event::Round { number } => format!(r#"{{"event":"round","number":{}}}"#, number),
+            Event::Move { unit, from, to } => format!(
+                r#"{{"event":"move","unit":"{}","from":[{},{}],"to":[{},{}]}}"#,
+                unit, from.row, from.col, to.row, to.col
+            ),
+            Event::Attack {
+                unit,
+                at,
+                target,
+                damage,
+                health,
+            } => format!(
+                r#"{{"event":"attack","unit":"{}","at":[{},{}],"target":[{},{}],"damage":{},"health":{}}}"#,
+                unit, at.row, at.col, target.row, target.col, damage, health
+            ),
+            Event::Death { unit, at } => format!(
+                r#"{{"event":"death","unit":"{}","at":[{},{}]}}"#,
+                unit, at.row, at.col
+            ),
+            Event::End {
+                rounds,
+                health,
+                outcome,
+            } => format!(
+                r#"{{"event":"end","rounds":{},"health":{},"outcome":{}}}"#,
+                rounds, health, outcome
+            ),
+        }
+    }
+}
+
+// Returns the kind of the unit at `target` if the hit killed it.
+fn strike<F>(grid: &mut Grid, at: Pos, target: Pos, emit: &mut F) -> Option<char>
+where
+    F: FnMut(Event, &Grid),
+{
+    let unit = grid.units[at]?;
+    let that = grid.units[target]?;
+    let after = unit.attack(that);
+    grid.units[target] = after;
+    let attack = Event::Attack {
+        unit: unit.kind,
+        at,
+        target,
+        damage: unit.attack,
+        health: after.map(|u| u.health).unwrap_or(0),
+    };
+    emit(attack, grid);
+    if after.is_none() {
+        let death = Event::Death {
+            unit: that.kind,
+            at: target,
+        };
+        emit(death, grid);
+        Some(that.kind)
+    } else {
+        None
+    }
+}
+
+// Every unit takes its turn in reading order of the positions at the start of the round.
+// Returns false when a unit finds no enemies left: combat ends and the round is incomplete,
+// or when a guarded unit dies.
+fn round<F: FnMut(Event, &Grid)>(grid: &mut Grid, emit: &mut F) -> bool {
+    let mut order = std::mem::take(&mut grid.order);
+    order.clear();
+    order.extend(
+        grid.units
+            .iter()
+            .filter(|(_, u)| u.is_some())
+            .map(|(p, _)| p),
+    );
+    grid.moved.clear();
+    grid.moved.resize(grid.cells.as_slice().len(), false);
+    let complete = turns(grid, &order, emit);
+    grid.order = order;
+    complete
+}
+
+fn turns<F: FnMut(Event, &Grid)>(grid: &mut Grid, order: &[Pos], emit: &mut F) -> bool {
+    for &p in order {
+        if grid.moved[grid.cells.index_of(p)] {
+            continue;
+        }
+        let u = match grid.units[p] {
+            Some(u) => u,
+            None => continue,
+        };
+        if !grid.has_enemies(u.kind) {
+            return false;
+        }
+        let killed = match grid.target(p) {
+            Some(t) if grid.units[t].is_some() => strike(grid, p, t, emit),
+            Some(t) => {
+                grid.units[t] = Some(u);
+                grid.units[p] = None;
+                grid.moved[grid.cells.index_of(t)] = true;
+                let step = Event::Move {
+                    unit: u.kind,
+                    from: p,
+                    to: t,
+                };
+                emit(step, grid);
+                match grid.adjacent(t) {
+                    Some(at) => strike(grid, t, at, emit),
+                    None => None,
+                }
+            }
+            None => None,
+        };
+        if killed.is_some() && killed == grid.guard {
+            return false;
+        }
+    }
+    true
+}
+
+// Runs the combat to the end, passing every event to `watch` along with the map
+// right after it. Returns the number of full rounds.
+fn combat<F: FnMut(Event, &Grid)>(grid: &mut Grid, mut watch: F) -> usize {
+    let mut rounds = 0;
+    loop {
+        watch(Event::Round { number: rounds + 1 }, grid);
+        if !round(grid, &mut watch) {
+            break;
+        }
+        rounds += 1;
+    }
+    let end = Event::End {
+        rounds,
+        health: health(grid),
+        outcome: outcome(grid, rounds),
+    };
+    watch(end, grid);
+    rounds
+}
+
+fn health(grid: &Grid) -> usize {
+    grid.units
+        .iter()
+        .filter_map(|(_, u)| *u)
+        .map(|u| u.health)
+        .sum()
+}
+
+// Outcome of the combat: number of full rounds times the health left.
+fn outcome(grid: &Grid, rounds: usize) -> usize {
+    rounds * health(grid)
+}
+
+#[cfg(test)]
+fn solve(grid: &mut Grid) -> usize {
+    let rounds = combat(grid, |_, _| ());
+    outcome(grid, rounds)
+}
+
+// Map at the start of every round and at the end, as the puzzle shows it.
+fn render<W: Write>(out: &mut W, event: Event, grid: &Grid) -> io::Result<()> {
+    let title = match event {
+        Event::Round { number: 1 } => "Initially:".to_string(),
+        Event::Round { number: 2 } => "After 1 round:".to_string(),
+        Event::Round { number } => format!("After {} rounds:", number - 1),
+        Event::End { rounds, .. } => {
+            let map = grid.dump().join("\n");
+            return writeln!(out, "Combat ends after {} full rounds:\n{}", rounds, map);
+        }
+        _ => return Ok(()),
+    };
+    writeln!(out, "{}\n{}\n", title, grid.dump().join("\n"))
+}
+
+// Outcome of the combat if no elf dies with the given attack power.
+fn flawless(scenario: &Scenario, attack: usize) -> Option<usize> {
+    let mut grid = scenario.with_attack('E', attack).grid();
+    grid.guard = Some('E');
+    let elves = grid.count('E');
+    let rounds = combat(&mut grid, |_, _| ());
+    if grid.count('E') == elves {
+        Some(outcome(&grid, rounds))
+    } else {
+        None
+    }
+}
+
+// Lowest attack power (from their own up to `cap`) that lets all the elves survive,
+// and the outcome. Assumes more power never hurts: doubles it until the elves win,
+// then bisects.
+fn rescue(scenario: &Scenario, cap: usize) -> Result<(usize, usize), String> {
+    let mut lo = scenario.rules.stats('E').attack;
+    let mut hi = lo;
+    let mut best = loop {
+        if let Some(outcome) = flawless(scenario, hi) {
+            break (hi, outcome);
+        }
+        if hi >= cap {
+            return Err(format!(
+                "elves can't win without losses with attack up to {}",
+                cap
+            ));
+        }
+        lo = hi;
+        hi = (hi * 2).max(hi + 1).min(cap);
+    };
+    // Wins at `best.0`, and loses at `lo` unless it's the same power.
+    while best.0 - lo > 1 {
+        let mid = lo + (best.0 - lo) / 2;
+        match flawless(scenario, mid) {
+            Some(outcome) => best = (mid, outcome),
+            None => lo = mid,
+        }
+    }
+    Ok(best)
+}
+
+impl Solution for Day15 {
+    type Input = Scenario;
+
+    fn parse(input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Scenario::parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> String {
+        let grid = &mut input.grid();
+        let rounds = combat(grid, |_, _| ());
+        outcome(grid, rounds).to_string()
+    }
+
+    // The map is traced every round, the events are logged as JSON Lines.
+    // Logging stops at the first failed write, which is then reported.
+    fn part1_logged(input: &Self::Input, logs: &mut Logs) -> io::Result<String> {
+        let mut grid = input.grid();
+        let mut result = Ok(());
+        let rounds = combat(&mut grid, |event, grid| {
+            if result.is_err() {
+                return;
+            }
+            if let Some(out) = logs.trace.as_mut() {
+                result = render(out, event, grid);
+            }
+            if let (Ok(()), Some(out)) = (&result, logs.events.as_mut()) {
+                result = writeln!(out, "{}", event.json());
+            }
+        });
+        result?;
+        Ok(outcome(&grid, rounds).to_string())
+    }
+
+    // Unless capped in the header, the elves' attack power goes up to the health
+    // of the toughest unit: any more changes nothing.
+    fn part2(input: &Self::Input) -> String {
+        Self::part2_logged(input, &mut Logs::default()).unwrap()
+    }
+
+    // The attack power found, or why there is none, goes to the trace.
+    fn part2_logged(input: &Self::Input, logs: &mut Logs) -> io::Result<String> {
+        let cap = input.rules.cap.unwrap_or_else(|| {
+            let grid = input.grid();
+            let toughest = grid.units.iter().filter_map(|(_, u)| *u).map(|u| u.health);
+            toughest.max().unwrap_or(0)
+        });
+        let rescued = rescue(input, cap);
+        if let Some(out) = logs.trace.as_mut() {
+            match &rescued {
+                Ok((attack, outcome)) => writeln!(
+                    out,
+                    "elves win without losses with attack {}, outcome {}",
+                    attack, outcome
+                )?,
+                Err(e) => writeln!(out, "{}", e)?,
+            }
+        }
+        Ok(match rescued {
+            Ok((_, outcome)) => outcome.to_string(),
+            Err(_) => "none".to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::tests::Shared;
+
+    fn wrap(lines: Vec<&'static str>) -> Vec<String> {
+        lines.into_iter().map(String::from).collect()
+    }
+
+    fn scenario(lines: Vec<&'static str>) -> Scenario {
+        Scenario::parse(&lines.join("\n")).unwrap()
+    }
+
+    fn make_grid(lines: Vec<&'static str>, health: usize, attack: usize) -> Grid {
+        Grid::parse(wrap(lines), |chr: char, _row: usize, _col: usize| {
+            if chr == 'E' || chr == 'G' {
+                Some(Unit {
+                    kind: chr,
+                    health,
+                    attack,
+                })
+            } else {
+                None
+            }
+        })
+    }
+
+    fn adj(grid: &Grid, pos: Pos) -> Vec<Pos> {
+        if grid.cells[pos] == '#' {
+            vec![]
+        } else {
+            grid.cells
+                .neighbours4(pos)
+                .filter(|p| grid.cells[*p] != '#')
+                .collect()
+        }
+    }
+
+    // Path from the unit at `pos` to the closest enemy, the first step is the move `step`
+    // should find: two searches, slow but straightforward, to check it against.
+    fn find(grid: &Grid, pos: Pos) -> Vec<Pos> {
+        use crate::search;
+
+        let kind = match grid.units[pos] {
+            Some(u) => u.kind,
+            None => return vec![],
+        };
+        let (rows, cols) = (grid.cells.rows(), grid.cells.cols());
+        let mut open = |p: Pos, out: &mut Vec<(Pos, usize)>| {
+            let free = adj(grid, p).into_iter();
+            out.extend(
+                free.filter(|q| *q == pos || grid.units[*q].is_none())
+                    .map(|q| (q, 1)),
+            );
+        };
+        let enemy = |p: Pos| adj(grid, p).into_iter().find(|q| grid.enemy(*q, kind));
+
+        let visits = grid::Grid::new(rows, cols, None);
+        let near = search::dijkstra(&mut open, &[pos], visits, |p| enemy(p).is_some(), |p| p);
+        let square = match near.goal {
+            Some(square) => square,
+            None => return vec![], // no path found
+        };
+
+        // Going back from the square, each step is the first in reading order
+        // among the ones closest to it, the first step included.
+        let visits = grid::Grid::new(rows, cols, None);
+        let back = search::dijkstra(&mut open, &[square], visits, |p| p == pos, |p| p);
+        let mut path = back.path(pos).unwrap_or_default();
+        path.reverse();
+        path.extend(enemy(square));
+        path
+    }
+
+    // Path found by `find`, and the same first move from `step` (or the same enemy
+    // to attack right away, when there is nowhere to move).
+    fn assert_path(grid: &Grid, pos: Pos, path: Vec<Pos>) {
+        assert_eq!(find(grid, pos), path);
+        match path.len() {
+            0 => assert_eq!(grid.target(pos), None),
+            2 => assert_eq!(grid.adjacent(pos), Some(path[1])),
+            _ => assert_eq!(grid.step(pos), Some(path[1])),
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = make_grid(vec!["#####", "#E.E#", "#.G.#", "#E.E#", "#####"], 10, 1);
+        let gr = &grid;
+        assert_eq!(
+            gr.cells.dump(),
+            wrap(vec!["#####", "#...#", "#...#", "#...#", "#####"])
+        );
+        let e = Unit {
+            kind: 'E',
+            health: 10,
+            attack: 1,
+        };
+        let g = Unit {
+            kind: 'G',
+            health: 10,
+            attack: 1,
+        };
+        let units: Vec<(Pos, Unit)> = gr
+            .units
+            .iter()
+            .filter_map(|(p, u)| u.map(|u| (p, u)))
+            .collect();
+        assert_eq!(
+            units,
+            vec![
+                (Pos { row: 1, col: 1 }, e),
+                (Pos { row: 1, col: 3 }, e),
+                (Pos { row: 2, col: 2 }, g),
+                (Pos { row: 3, col: 1 }, e),
+                (Pos { row: 3, col: 3 }, e),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_target_1() {
+        let grid = make_grid(
+            vec![
+                "#######", "#E...E#", "#.....#", "#..G..#", "#.....#", "#E...E#", "#######",
+            ],
+            10,
+            1,
+        );
+
+        assert_eq!(
+            grid.target(Pos { row: 3, col: 3 }),
+            Some(Pos { row: 2, col: 3 })
+        );
+        assert_eq!(
+            grid.target(Pos { row: 1, col: 1 }),
+            Some(Pos { row: 1, col: 2 })
+        );
+        assert_eq!(
+            grid.target(Pos { row: 1, col: 5 }),
+            Some(Pos { row: 1, col: 4 })
+        );
+        assert_eq!(
+            grid.target(Pos { row: 5, col: 1 }),
+            Some(Pos { row: 4, col: 1 })
+        );
+        assert_eq!(
+            grid.target(Pos { row: 5, col: 5 }),
+            Some(Pos { row: 4, col: 5 })
+        );
+    }
+
+    #[test]
+    fn test_find_2() {
+        let grid = make_grid(vec!["#####", "#...#", "#GG.#", "#..E#", "#####"], 10, 1);
+
+        let pos = Pos { row: 2, col: 2 };
+        assert_path(
+            &grid,
+            pos,
+            vec![
+                Pos { row: 2, col: 2 },
+                Pos { row: 2, col: 3 },
+                Pos { row: 3, col: 3 },
+            ],
+        );
+    }
+
+    #[test]
+    fn test_find_3() {
+        let grid = make_grid(vec!["#####", "#...#", "#GG.#", "#..E#", "#####"], 10, 1);
+
+        let pos = Pos { row: 2, col: 1 };
+        assert_path(
+            &grid,
+            pos,
+            vec![
+                Pos { row: 2, col: 1 },
+                Pos { row: 3, col: 1 },
+                Pos { row: 3, col: 2 },
+                Pos { row: 3, col: 3 },
+            ],
+        );
+    }
+
+    #[test]
+    fn test_find_4() {
+        let grid = make_grid(vec!["#####", "#...#", "#GGE#", "#...#", "#####"], 10, 1);
+
+        let pos = Pos { row: 2, col: 1 };
+        assert_path(
+            &grid,
+            pos,
+            vec![
+                Pos { row: 2, col: 1 },
+                Pos { row: 1, col: 1 },
+                Pos { row: 1, col: 2 },
+                Pos { row: 1, col: 3 },
+                Pos { row: 2, col: 3 },
+            ],
+        );
+    }
+
+    #[test]
+    fn test_find_5() {
+        let grid = make_grid(
+            vec!["#####", "#...#", "#.G.#", "#.G.#", "#.E.#", "#####"],
+            10,
+            1,
+        );
+
+        let pos = Pos { row: 2, col: 2 };
+        assert_path(
+            &grid,
+            pos,
+            vec![
+                Pos { row: 2, col: 2 },
+                Pos { row: 2, col: 1 },
+                Pos { row: 3, col: 1 },
+                Pos { row: 4, col: 1 },
+                Pos { row: 4, col: 2 },
+            ],
+        );
+    }
+
+    #[test]
+    fn test_find_triangle_up1() {
+        let grid = make_grid(
+            vec![
+                "#######", "#G...E#", "#...E.#", "#..E..#", "#.E...#", "#E....#", "#######",
+            ],
+            10,
+            1,
+        );
+
+        let pos = Pos { row: 1, col: 1 };
+        assert_path(
+            &grid,
+            pos,
+            vec![
+                Pos { row: 1, col: 1 },
+                Pos { row: 1, col: 2 },
+                Pos { row: 1, col: 3 },
+                Pos { row: 1, col: 4 },
+                Pos { row: 1, col: 5 },
+            ],
+        );
+    }
+
+    #[test]
+    fn test_find_triangle_up2() {
+        let grid = make_grid(
+            vec![
+                "#######", "#E...G#", "#.E...#", "#..E..#", "#...E.#", "#....E#", "#######",
+            ],
+            10,
+            1,
+        );
+
+        let pos = Pos { row: 1, col: 5 };
+        assert_path(
+            &grid,
+            pos,
+            vec![
+                Pos { row: 1, col: 5 },
+                Pos { row: 1, col: 4 },
+                Pos { row: 1, col: 3 },
+                Pos { row: 1, col: 2 },
+                Pos { row: 1, col: 1 },
+            ],
+        );
+    }
+
+    #[test]
+    fn test_find_triangle_dn1() {
+        let grid = make_grid(
+            vec![
+                "#######", "#....E#", "#...E.#", "#..E..#", "#.E...#", "#E...G#", "#######",
+            ],
+            10,
+            1,
+        );
+
+        let pos = Pos { row: 5, col: 5 };
+        assert_path(
+            &grid,
+            pos,
+            vec![
+                Pos { row: 5, col: 5 },
+                Pos { row: 4, col: 5 },
+                Pos { row: 3, col: 5 },
+                Pos { row: 2, col: 5 },
+                Pos { row: 1, col: 5 },
+            ],
+        );
+    }
+
+    #[test]
+    fn test_find_triangle_dn2() {
+        let grid = make_grid(
+            vec![
+                "#######", "#E....#", "#.E...#", "#..E..#", "#...E.#", "#G...E#", "#######",
+            ],
+            10,
+            1,
+        );
+
+        let pos = Pos { row: 5, col: 1 };
+        assert_path(
+            &grid,
+            pos,
+            vec![
+                Pos { row: 5, col: 1 },
+                Pos { row: 4, col: 1 },
+                Pos { row: 3, col: 1 },
+                Pos { row: 2, col: 1 },
+                Pos { row: 1, col: 1 },
+            ],
+        );
+    }
+
+    #[test]
+    fn test_find_diamond1() {
+        let grid = make_grid(
+            vec![
+                "###########",
+                "#....E....#",
+                "#...E.E...#",
+                "#..E...E..#",
+                "#.E.....E.#",
+                "#E...G...E#",
+                "#.E.....E.#",
+                "#..E...E..#",
+                "#...E.E...#",
+                "#....E....#",
+                "###########",
+            ],
+            10,
+            1,
+        );
+
+        let pos = Pos { row: 5, col: 5 };
+        assert_path(
+            &grid,
+            pos,
+            vec![
+                Pos { row: 5, col: 5 },
+                Pos { row: 4, col: 5 },
+                Pos { row: 3, col: 5 },
+                Pos { row: 2, col: 5 },
+                Pos { row: 1, col: 5 },
+            ],
+        );
+    }
+
+    #[test]
+    fn test_find_diamond2() {
+        let grid = make_grid(
+            vec![
+                "###########",
+                "#....E....#",
+                "#...E#E...#",
+                "#..E...E..#",
+                "#.E.....E.#",
+                "#E...G...E#",
+                "#.E.....E.#",
+                "#..E...E..#",
+                "#...E.E...#",
+                "#....E....#",
+                "###########",
+            ],
+            10,
+            1,
+        );
+
+        let pos = Pos { row: 5, col: 5 };
+        assert_path(
+            &grid,
+            pos,
+            vec![
+                Pos { row: 5, col: 5 },
+                Pos { row: 4, col: 5 },
+                Pos { row: 3, col: 5 },
+                Pos { row: 3, col: 4 },
+                Pos { row: 2, col: 4 },
+            ],
+        );
+    }
+
+    #[test]
+    fn test_find_diamond3() {
+        let grid = make_grid(
+            vec![
+                "###########",
+                "#....E....#",
+                "#...E#E...#",
+                "#..E##.E..#", // (3,6) must be chosen, as first in reading order
+                "#.E..G..E.#",
+                "#E.......E#",
+                "#.E.....E.#",
+                "#..E...E..#",
+                "#...E.E...#",
+                "#....E....#",
+                "###########",
+            ],
+            10,
+            1,
+        );
+
+        let pos = Pos { row: 4, col: 5 };
+        assert_path(
+            &grid,
+            pos,
+            vec![
+                Pos { row: 4, col: 5 },
+                Pos { row: 4, col: 6 },
+                Pos { row: 3, col: 6 },
+                Pos { row: 2, col: 6 },
+            ],
+        );
+    }
+
+    #[test]
+    fn test_find_second() {
+        let grid = make_grid(
+            vec![
+                "#########", //  "#########",
+                "#...E...#", //  "#.@@E...#",
+                "#....E..#", //  "#.@..E..#",
+                "#..######", //  "#.@######",
+                "#.......#", //  "#.@@@...#",
+                "#...G...#", //  "#...G...#",
+                "#..######", //  "#..######",
+                "#.......#", //  "#.......#",
+                "#....E..#", //  "#....E..#",
+                "#########", //  "#########",
+            ],
+            10,
+            1,
+        );
+
+        let pos = Pos { row: 5, col: 4 };
+        assert_path(
+            &grid,
+            pos,
+            vec![
+                Pos { row: 5, col: 4 },
+                Pos { row: 4, col: 4 },
+                Pos { row: 4, col: 3 },
+                Pos { row: 4, col: 2 },
+                Pos { row: 3, col: 2 },
+                Pos { row: 2, col: 2 },
+                Pos { row: 1, col: 2 },
+                Pos { row: 1, col: 3 },
+                Pos { row: 1, col: 4 },
+            ],
+        );
+    }
+
+    #[test]
+    fn test_target1() {
+        let grid = make_grid(
+            vec!["#######", "#E..G.#", "#...#.#", "#.G.#G#", "#######"],
+            10,
+            1,
+        );
+
+        let pos = Pos { row: 1, col: 1 };
+        assert_eq!(grid.target(pos), Some(Pos { row: 1, col: 2 }));
+    }
+
+    #[test]
+    fn test_target2_1() {
+        let grid = make_grid(
+            vec!["#######", "#.E...#", "#.....#", "#...G.#", "#######"],
+            10,
+            1,
+        );
+
+        let pos = Pos { row: 1, col: 2 };
+        assert_eq!(grid.target(pos), Some(Pos { row: 1, col: 3 }));
+    }
+
+    #[test]
+    fn test_target2_2() {
+        let grid = make_grid(
+            vec!["#######", "#..E..#", "#.....#", "#...G.#", "#######"],
+            10,
+            1,
+        );
+
+        let pos = Pos { row: 1, col: 3 };
+        assert_eq!(grid.target(pos), Some(Pos { row: 1, col: 4 }));
+    }
+
+    #[test]
+    fn test_target2_3() {
+        let grid = make_grid(
+            vec!["#######", "#...E.#", "#.....#", "#...G.#", "#######"],
+            10,
+            1,
+        );
+
+        let pos = Pos { row: 1, col: 4 };
+        assert_eq!(grid.target(pos), Some(Pos { row: 2, col: 4 }));
+    }
+
+    #[test]
+    fn test_target2_4() {
+        let grid = make_grid(
+            vec!["#######", "#.....#", "#...E.#", "#...G.#", "#######"],
+            10,
+            1,
+        );
+
+        let pos = Pos { row: 2, col: 4 };
+        assert_eq!(grid.target(pos), Some(Pos { row: 3, col: 4 }));
+    }
+
+    #[test]
+    fn test_find_between2() {
+        let grid = make_grid(vec!["#####", "#..E#", "#G..#", "#..E#", "#####"], 10, 1);
+
+        let pos = Pos { row: 2, col: 1 };
+        assert_path(
+            &grid,
+            pos,
+            vec![
+                Pos { row: 2, col: 1 },
+                Pos { row: 1, col: 1 },
+                Pos { row: 1, col: 2 },
+                Pos { row: 1, col: 3 },
+            ],
+        );
+    }
+
+    #[test]
+    fn test_find_between4_corners() {
+        let grid = make_grid(
+            vec![
+                "#######", "#E...E#", "#.....#", "#..G..#", "#.....#", "#E...E#", "#######",
+            ],
+            10,
+            1,
+        );
+
+        let pos = Pos { row: 3, col: 3 };
+        assert_path(
+            &grid,
+            pos,
+            vec![
+                Pos { row: 3, col: 3 },
+                Pos { row: 2, col: 3 },
+                Pos { row: 1, col: 3 },
+                Pos { row: 1, col: 2 },
+                Pos { row: 1, col: 1 },
+            ],
+        );
+    }
+
+    #[test]
+    fn test_find_between4_edges() {
+        let grid = make_grid(
+            vec![
+                "#######", "#..E..#", "#.....#", "#E.G.E#", "#.....#", "#..E..#", "#######",
+            ],
+            10,
+            1,
+        );
+
+        let pos = Pos { row: 3, col: 3 };
+        assert_path(
+            &grid,
+            pos,
+            vec![
+                Pos { row: 3, col: 3 },
+                Pos { row: 2, col: 3 },
+                Pos { row: 1, col: 3 },
+            ],
+        );
+    }
+
+    #[test]
+    fn test_find_between4_wall1() {
+        let grid = make_grid(
+            vec![
+                "#######", "#..E..#", "#..####", "#E.G.E#", "#.....#", "#..E..#", "#######",
+            ],
+            10,
+            1,
+        );
+
+        let pos = Pos { row: 3, col: 3 };
+        assert_path(
+            &grid,
+            pos,
+            vec![
+                Pos { row: 3, col: 3 },
+                Pos { row: 3, col: 2 },
+                Pos { row: 3, col: 1 },
+            ],
+        );
+    }
+
+    #[test]
+    fn test_find_between4_wall2() {
+        let grid = make_grid(
+            vec![
+                "#######", "#..E..#", "#.#####", "#E#G.E#", "#.#...#", "#..E..#", "#######",
+            ],
+            10,
+            1,
+        );
+
+        let pos = Pos { row: 3, col: 3 };
+        assert_path(
+            &grid,
+            pos,
+            vec![
+                Pos { row: 3, col: 3 },
+                Pos { row: 3, col: 4 },
+                Pos { row: 3, col: 5 },
+            ],
+        );
+    }
+
+    #[test]
+    fn test_find_between4_wall3() {
+        let grid = make_grid(
+            vec![
+                "#######", "#..E..#", "#.#####", "#E#G#E#", "#.#...#", "#..E..#", "#######",
+            ],
+            10,
+            1,
+        );
+
+        let pos = Pos { row: 3, col: 3 };
+        assert_path(
+            &grid,
+            pos,
+            vec![
+                Pos { row: 3, col: 3 },
+                Pos { row: 4, col: 3 },
+                Pos { row: 5, col: 3 },
+            ],
+        );
+    }
+
+    #[test]
+    fn test_solve1() {
+        let mut grid = make_grid(
+            vec![
+                "#######", "#.G...#", "#...EG#", "#.#.#G#", "#..G#E#", "#.....#", "#######",
+            ],
+            200,
+            3,
+        );
+
+        assert_eq!(solve(&mut grid), 27730);
+    }
+
+    #[test]
+    fn test_trace() {
+        let mut grid = scenario(vec![
+            "#######", "#.G...#", "#...EG#", "#.#.#G#", "#..G#E#", "#.....#", "#######",
+        ])
+        .grid();
+        let mut out = Vec::new();
+        let rounds = combat(&mut grid, |event, grid| {
+            render(&mut out, event, grid).unwrap()
+        });
+        assert_eq!(outcome(&grid, rounds), 27730);
+        let out = String::from_utf8(out).unwrap();
+        let rounds: Vec<&str> = out.split("\n\n").collect();
+        assert_eq!(rounds.len(), 49);
+        assert_eq!(
+            rounds[0],
+            [
+                "Initially:",
+                "#######",
+                "#.G...#   G(200)",
+                "#...EG#   E(200), G(200)",
+                "#.#.#G#   G(200)",
+                "#..G#E#   G(200), E(200)",
+                "#.....#",
+                "#######",
+            ]
+            .join("\n")
+        );
+        assert_eq!(
+            rounds[2],
+            [
+                "After 2 rounds:",
+                "#######",
+                "#...G.#   G(200)",
+                "#..GEG#   G(200), E(188), G(194)",
+                "#.#.#G#   G(194)",
+                "#...#E#   E(194)",
+                "#.....#",
+                "#######",
+            ]
+            .join("\n")
+        );
+        assert_eq!(
+            rounds[48],
+            [
+                "Combat ends after 47 full rounds:",
+                "#######",
+                "#G....#   G(200)",
+                "#.G...#   G(131)",
+                "#.#.#G#   G(59)",
+                "#...#.#",
+                "#....G#   G(200)",
+                "#######",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_events() {
+        let mut grid = scenario(vec![
+            "#######", "#.G...#", "#...EG#", "#.#.#G#", "#..G#E#", "#.....#", "#######",
+        ])
+        .grid();
+        let mut events = Vec::new();
+        combat(&mut grid, |event, _| events.push(event));
+
+        let at = |row, col| Pos { row, col };
+        let attack = |unit, from, target, health| Event::Attack {
+            unit,
+            at: from,
+            target,
+            damage: 3,
+            health,
+        };
+        assert_eq!(
+            events[..7].to_vec(),
+            vec![
+                Event::Round { number: 1 },
+                Event::Move {
+                    unit: 'G',
+                    from: at(1, 2),
+                    to: at(1, 3)
+                },
+                attack('E', at(2, 4), at(2, 5), 197),
+                attack('G', at(2, 5), at(2, 4), 197),
+                attack('G', at(3, 5), at(4, 5), 197),
+                Event::Move {
+                    unit: 'G',
+                    from: at(4, 3),
+                    to: at(3, 3)
+                },
+                attack('E', at(4, 5), at(3, 5), 197),
+            ]
+        );
+        let deaths = events
+            .iter()
+            .filter(|e| matches!(e, Event::Death { unit: 'E', .. }))
+            .count();
+        assert_eq!(deaths, 2);
+        let end = Event::End {
+            rounds: 47,
+            health: 590,
+            outcome: 27730,
+        };
+        assert_eq!(events.last(), Some(&end));
+
+        assert_eq!(
+            events[1].json(),
+            r#"{"event":"move","unit":"G","from":[1,2],"to":[1,3]}"#
+        );
+        assert_eq!(
+            events[2].json(),
+            r#"{"event":"attack","unit":"E","at":[2,4],"target":[2,5],"damage":3,"health":197}"#
+        );
+        assert_eq!(
+            end.json(),
+            r#"{"event":"end","rounds":47,"health":590,"outcome":27730}"#
+        );
+    }
+
+    #[test]
+    fn test_solve2() {
+        let mut grid = make_grid(
+            vec![
+                "#######", "#G..#E#", "#E#E.E#", "#G.##.#", "#...#E#", "#...E.#", "#######",
+            ],
+            200,
+            3,
+        );
+
+        let x = solve(&mut grid);
+
+        assert_eq!(
+            grid.dump(),
+            wrap(vec![
+                "#######",
+                "#...#E#   E(200)",
+                "#E#...#   E(197)",
+                "#.E##.#   E(185)",
+                "#E..#E#   E(200), E(200)",
+                "#.....#",
+                "#######",
+            ])
+        );
+        assert_eq!(x, 36334);
+    }
+
+    #[test]
+    fn test_solve3() {
+        let mut grid = make_grid(
+            vec![
+                "#######", "#E..EG#", "#.#G.E#", "#E.##E#", "#G..#.#", "#..E#.#", "#######",
+            ],
+            200,
+            3,
+        );
+
+        let x = solve(&mut grid);
+
+        assert_eq!(
+            grid.dump(),
+            wrap(vec![
+                "#######",
+                "#.E.E.#   E(164), E(197)",
+                "#.#E..#   E(200)",
+                "#E.##.#   E(98)",
+                "#.E.#.#   E(200)",
+                "#...#.#",
+                "#######",
+            ])
+        );
+        assert_eq!(x, 39514);
+    }
+
+    #[test]
+    fn test_solve4() {
+        let mut grid = make_grid(
+            vec![
+                "#######", "#E.G#.#", "#.#G..#", "#G.#.G#", "#G..#.#", "#...E.#", "#######",
+            ],
+            200,
+            3,
+        );
+        assert_eq!(solve(&mut grid), 27755);
+    }
+
+    #[test]
+    fn test_solve5() {
+        let mut grid = make_grid(
+            vec![
+                "#######", "#.E...#", "#.#..G#", "#.###.#", "#E#G#G#", "#...#G#", "#######",
+            ],
+            200,
+            3,
+        );
+        assert_eq!(solve(&mut grid), 28944);
+    }
+
+    #[test]
+    fn test_solve_not_square() {
+        // The first example walled in on a wider and taller map.
+        let mut grid = make_grid(
+            vec![
+                "##########",
+                "#.G...####",
+                "#...EG####",
+                "#.#.#G####",
+                "#..G#E####",
+                "#.....####",
+                "##########",
+                "##########",
+            ],
+            200,
+            3,
+        );
+        assert_eq!(solve(&mut grid), 27730);
+    }
+
+    #[test]
+    fn test_solve6() {
+        let mut grid = make_grid(
+            vec![
+                "#########",
+                "#G......#",
+                "#.E.#...#",
+                "#..##..G#",
+                "#...##..#",
+                "#...#...#",
+                "#.G...G.#",
+                "#.....G.#",
+                "#########",
+            ],
+            200,
+            3,
+        );
+        let x = solve(&mut grid);
+
+        assert_eq!(
+            grid.dump(),
+            wrap(vec![
+                "#########",
+                "#.G.....#   G(137)",
+                "#G.G#...#   G(200), G(200)",
+                "#.G##...#   G(200)",
+                "#...##..#",
+                "#.G.#...#   G(200)",
+                "#.......#",
+                "#.......#",
+                "#########",
+            ])
+        );
+
+        assert_eq!(x, 18740);
+    }
+
+    fn examples() -> Vec<Scenario> {
+        let maps = vec![
+            vec![
+                "#######", "#.G...#", "#...EG#", "#.#.#G#", "#..G#E#", "#.....#", "#######",
+            ],
+            vec![
+                "#######", "#G..#E#", "#E#E.E#", "#G.##.#", "#...#E#", "#...E.#", "#######",
+            ],
+            vec![
+                "#######", "#E..EG#", "#.#G.E#", "#E.##E#", "#G..#.#", "#..E#.#", "#######",
+            ],
+            vec![
+                "#######", "#E.G#.#", "#.#G..#", "#G.#.G#", "#G..#.#", "#...E.#", "#######",
+            ],
+            vec![
+                "#######", "#.E...#", "#.#..G#", "#.###.#", "#E#G#G#", "#...#G#", "#######",
+            ],
+            vec![
+                "#########",
+                "#G......#",
+                "#.E.#...#",
+                "#..##..G#",
+                "#...##..#",
+                "#...#...#",
+                "#.G...G.#",
+                "#.....G.#",
+                "#########",
+            ],
+            vec![
+                "allies: A, B",
+                "C: hp 300",
+                "#########",
+                "#A..C...#",
+                "#...#.B.#",
+                "#C..#...#",
+                "#..D..A.#",
+                "#B.....C#",
+                "#########",
+            ],
+        ];
+        maps.into_iter().map(scenario).collect()
+    }
+
+    // Map and units at the start of every round of the combat.
+    fn rounds(scenario: &Scenario) -> Vec<(Grid, Vec<Pos>)> {
+        let mut grid = scenario.grid();
+        let mut states = Vec::new();
+        combat(&mut grid, |event, grid| {
+            if let Event::Round { .. } = event {
+                let units = grid.units.iter().filter(|(_, u)| u.is_some());
+                states.push((grid.clone(), units.map(|(p, _)| p).collect()));
+            }
+        });
+        states
+    }
+
+    // Same moves with `step` as with `find`, for every unit all the way through the combat.
+    #[test]
+    fn test_step() {
+        for scenario in examples() {
+            for (grid, units) in rounds(&scenario) {
+                for pos in units.into_iter().filter(|p| grid.adjacent(*p).is_none()) {
+                    assert_eq!(grid.step(pos), find(&grid, pos).get(1).copied());
+                }
+            }
+        }
+    }
+
+    // Moves of the units at the start of every round with `find` and with `step`,
+    // on the examples and the input (if any):
+    //   cargo test --release day15::tests::bench -- --ignored --nocapture
+    #[test]
+    #[ignore]
+    fn bench() {
+        use std::time::Instant;
+
+        let mut runs: Vec<(String, Scenario)> = examples()
+            .into_iter()
+            .enumerate()
+            .map(|(i, s)| (format!("example {}", i + 1), s))
+            .collect();
+        if let Ok(input) = std::fs::read_to_string("inputs/day15.txt") {
+            runs.push(("input".to_string(), Day15::parse(&input).unwrap()));
+        }
+
+        for (name, scenario) in runs {
+            let states = rounds(&scenario);
+            let time = |f: &dyn Fn(&Grid, Pos) -> Option<Pos>| {
+                let start = Instant::now();
+                let moves: Vec<Option<Pos>> = states
+                    .iter()
+                    .flat_map(|(grid, units)| {
+                        let moving = units.iter().filter(move |p| grid.adjacent(**p).is_none());
+                        moving.map(move |p| f(grid, *p))
+                    })
+                    .collect();
+                (moves, start.elapsed())
+            };
+            let (slow, before) = time(&|grid, p| find(grid, p).get(1).copied());
+            let (fast, after) = time(&|grid, p| grid.step(p));
+            assert_eq!(slow, fast);
+            println!(
+                "{:>10}: {:>10.2?} -> {:>10.2?} ({:.1}x)",
+                name,
+                before,
+                after,
+                before.as_secs_f64() / after.as_secs_f64()
+            );
+        }
+    }
+
+    #[test]
+    fn test_rescue() {
+        let maps = vec![
+            (
+                vec![
+                    "#######", "#.G...#", "#...EG#", "#.#.#G#", "#..G#E#", "#.....#", "#######",
+                ],
+                15,
+                4988,
+            ),
+            (
+                vec![
+                    "#######", "#E..EG#", "#.#G.E#", "#E.##E#", "#G..#.#", "#..E#.#", "#######",
+                ],
+                4,
+                31284,
+            ),
+            (
+                vec![
+                    "#######", "#E.G#.#", "#.#G..#", "#G.#.G#", "#G..#.#", "#...E.#", "#######",
+                ],
+                15,
+                3478,
+            ),
+            (
+                vec![
+                    "#######", "#.E...#", "#.#..G#", "#.###.#", "#E#G#G#", "#...#G#", "#######",
+                ],
+                12,
+                6474,
+            ),
+            (
+                vec![
+                    "#########",
+                    "#G......#",
+                    "#.E.#...#",
+                    "#..##..G#",
+                    "#...##..#",
+                    "#...#...#",
+                    "#.G...G.#",
+                    "#.....G.#",
+                    "#########",
+                ],
+                34,
+                1140,
+            ),
+        ];
+        for (map, attack, outcome) in maps {
+            assert_eq!(rescue(&scenario(map), 200), Ok((attack, outcome)));
+        }
+
+        let first = scenario(vec![
+            "#######", "#.G...#", "#...EG#", "#.#.#G#", "#..G#E#", "#.....#", "#######",
+        ]);
+        assert!(rescue(&first, 10).is_err());
+        assert_eq!(flawless(&first, 14), None);
+        let capped = format!("cap: 14\n{}", first.map.join("\n"));
+        let capped = Day15::parse(&capped).unwrap();
+        assert_eq!(Day15::part2(&capped), "none");
+        // The reason is in the trace.
+        let shared = Shared::default();
+        let mut logs = Logs {
+            trace: Some(Box::new(shared.clone())),
+            events: None,
+        };
+        assert_eq!(Day15::part2_logged(&capped, &mut logs).unwrap(), "none");
+        assert_eq!(
+            shared.text(),
+            "elves can't win without losses with attack up to 14\n"
+        );
+
+        // Tough enough elf wins at its own attack power: 67 rounds, 102 HP left.
+        let own = "E: hp 300\n#####\n#EG.#\n#####";
+        let own = Day15::parse(own).unwrap();
+        assert_eq!(rescue(&own, 200), Ok((3, 67 * 102)));
+        assert_eq!(Day15::part2(&own), (67 * 102).to_string());
+    }
+
+    #[test]
+    fn test_guard() {
+        let mut grid = make_grid(vec!["#####", "#GEG#", "#####"], 9, 3);
+        grid.guard = Some('E');
+        assert!(round(&mut grid, &mut |_, _| ()));
+        // The elf dies on the second hit of the left goblin, before the right one acts.
+        assert!(!round(&mut grid, &mut |_, _| ()));
+        assert_eq!(
+            grid.dump(),
+            wrap(vec!["#####", "#G.G#   G(6), G(9)", "#####"])
+        );
+    }
+
+    #[test]
+    fn test_rules() {
+        let scenario =
+            Day15::parse("E: hp 300, attack 5\nallies: D, E\nX: attack 1\n#DEGX#\n").unwrap();
+        assert_eq!(
+            scenario.rules.stats('E'),
+            Stats {
+                health: 300,
+                attack: 5
+            }
+        );
+        assert_eq!(
+            scenario.rules.stats('X'),
+            Stats {
+                health: 200,
+                attack: 1
+            }
+        );
+        assert_eq!(scenario.rules.stats('G'), DEFAULT_STATS);
+        assert_eq!(scenario.rules.cap, None);
+        assert_eq!(
+            Day15::parse(
+                "cap: 40
+#E#"
+            )
+            .unwrap()
+            .rules
+            .cap,
+            Some(40)
+        );
+        assert!(!scenario.rules.hostile('D', 'E'));
+        assert!(!scenario.rules.hostile('E', 'D'));
+        assert!(scenario.rules.hostile('D', 'G'));
+        assert!(scenario.rules.hostile('X', 'G'));
+        assert_eq!(
+            scenario.grid().dump(),
+            vec!["#DEGX#   D(200), E(300), G(200), X(200)"]
+        );
+
+        for bad in [
+            "E: hp lots\n#E#",
+            "E: speed 3\n#E#",
+            "allies: E, GG\n#E#",
+            "cap: -1\n#E#",
+            "E: attack 0\nG: attack 0\n#####\n#E.G#\n#####",
+            "E hp 3\n#E#",
+            "#E?G#",
+            "E: hp 3",
+        ]
+        .iter()
+        {
+            assert!(Day15::parse(bad).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_factions() {
+        // D can't reach G past its ally E, and ends the combat when G is gone.
+        let text = "allies: D, E\nG: hp 9\n#####\n#DEG#\n#####";
+        let scenario = Day15::parse(text).unwrap();
+        assert_eq!(Day15::part1(&scenario), (3 * (200 + 194)).to_string());
+
+        // Without the alliance D attacks E right away, E still goes for the weaker G.
+        let text = "G: hp 9\n#####\n#DEG#\n#####";
+        let mut grid = Day15::parse(text).unwrap().grid();
+        assert!(round(&mut grid, &mut |_, _| ()));
+        assert_eq!(
+            grid.dump(),
+            vec!["#####", "#DEG#   D(200), E(194), G(6)", "#####"]
+        );
+    }
+
+    #[test]
+    fn test_simulation() {
+        let mut grid = make_grid(
+            vec![
+                "#########",
+                "#G..G..G#   G(10), G(10), G(10)",
+                "#.......#",
+                "#.......#",
+                "#G..E..G#   G(10), E(10), G(10)",
+                "#.......#",
+                "#.......#",
+                "#G..G..G#   G(10), G(10), G(10)",
+                "#########",
+            ],
+            10,
+            2,
+        );
+
+        assert!(round(&mut grid, &mut |_, _| ()));
+        assert_eq!(
+            grid.dump(),
+            wrap(vec![
+                "#########",
+                "#.G...G.#   G(10), G(10)",
+                "#...G...#   G(8)",
+                "#...E..G#   E(10), G(10)",
+                "#.G.....#   G(10)",
+                "#.......#",
+                "#G..G..G#   G(10), G(10), G(10)",
+                "#.......#",
+                "#########",
+            ])
+        );
+
+        assert!(round(&mut grid, &mut |_, _| ()));
+        assert_eq!(
+            grid.dump(),
+            wrap(vec![
+                "#########",
+                "#..G.G..#   G(10), G(10)",
+                "#...G...#   G(6)",
+                "#.G.E.G.#   G(10), E(8), G(10)",
+                "#.......#",
+                "#G..G..G#   G(10), G(10), G(10)",
+                "#.......#",
+                "#.......#",
+                "#########",
+            ])
+        );
+
+        // The elf dies before every goblin got its turn.
+        assert!(!round(&mut grid, &mut |_, _| ()));
+        assert_eq!(
+            grid.dump(),
+            wrap(vec![
+                "#########",
+                "#.......#",
+                "#..GGG..#   G(10), G(4), G(10)",
+                "#..G.G..#   G(10), G(10)",
+                "#G..G...#   G(10), G(10)",
+                "#......G#   G(10)",
+                "#.......#",
+                "#.......#",
+                "#########",
+            ])
+        );
+        assert_eq!(grid.count('E'), 0);
+        assert_eq!(grid.count('G'), 8);
+    }
+}
@@ -0,0 +1,72 @@
+use std::error::Error;
+
+use super::Solution;
+use crate::elfcode::infer::{Inference, Mapping, RawCode, Solver};
+use crate::elfcode::samples::Samples;
+use crate::elfcode::{Code, Machine};
+
+pub struct Day16;
+
+type State = Machine<4>;
+
+fn process(codes: &[RawCode], state: State, mapping: Mapping) -> State {
+    let codes: Vec<Code> = codes
+        .iter()
+        .map(|code| code.with(mapping[code.op]))
+        .collect();
+    let mut st = state;
+    st.run(&codes);
+    st
+}
+
+// Gives up listing alternatives beyond this many consistent mappings.
+const LIMIT: usize = 10;
+
+impl Solution for Day16 {
+    type Input = Samples;
+
+    fn parse(input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(Samples::parse(input)?)
+    }
+
+    // Samples that behave like three or more opcodes.
+    fn part1(input: &Self::Input) -> String {
+        input
+            .records
+            .iter()
+            .filter(|rec| rec.candidates().len() >= 3)
+            .count()
+            .to_string()
+    }
+
+    // Fixed answers when the samples don't pin down a single mapping.
+    fn part2(input: &Self::Input) -> String {
+        match Solver::new(&input.records).solve(LIMIT) {
+            Inference::Unique(mapping) => {
+                let zero = State::new(None, [0, 0, 0, 0]);
+                process(&input.program, zero, mapping).get(0).to_string()
+            }
+            Inference::Ambiguous { .. } => "ambiguous".to_string(),
+            Inference::Inconsistent(_) => "none".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part2_failures() {
+        // A single sample fits several opcodes.
+        let ambiguous =
+            Samples::parse("Before: [3, 2, 1, 1]\n9 2 1 2\nAfter:  [3, 2, 2, 1]\n\n\n\n9 0 0 0")
+                .unwrap();
+        assert_eq!(Day16::part2(&ambiguous), "ambiguous");
+        // Setting a register to 7 out of nowhere fits no opcode.
+        let none =
+            Samples::parse("Before: [0, 0, 0, 0]\n0 0 0 0\nAfter:  [7, 0, 0, 0]\n\n\n\n0 0 0 0")
+                .unwrap();
+        assert_eq!(Day16::part2(&none), "none");
+    }
+}
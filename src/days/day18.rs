@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+use std::error::Error;
+
+use super::Solution;
+use crate::geom::Pos;
+use crate::grid;
+
+pub struct Day18;
+
+type Grid = grid::Grid<char>;
+
+// Each acre can be either open ground (.), trees (|), or a lumberyard (#).
+// Here, "adjacent" means any of the eight acres surrounding that acre.
+
+/*
+An open acre will become filled with trees if three or more adjacent acres contained trees.
+Otherwise, nothing happens.
+
+An acre filled with trees will become a lumberyard if three or more adjacent acres were lumberyards.
+Otherwise, nothing happens.
+
+An acre containing a lumberyard will remain a lumberyard if it was adjacent to at least one other
+lumberyard and at least one acre containing trees. Otherwise, it becomes open.
+*/
+
+fn count(grid: &Grid, pos: Pos) -> (usize, usize, usize) {
+    let adj: Vec<char> = grid.neighbours8(pos).map(|p| grid[p]).collect();
+    let gr = adj.iter().filter(|c| **c == '.').count();
+    let tr = adj.iter().filter(|c| **c == '|').count();
+    let ly = adj.iter().filter(|c| **c == '#').count();
+    (gr, tr, ly)
+}
+
+fn mutate(grid: &Grid, pos: Pos) -> char {
+    let c = grid[pos];
+    match (c, count(grid, pos)) {
+        ('.', (_gr, tr, _ly)) if tr >= 3 => '|',
+        ('|', (_gr, _tr, ly)) if ly >= 3 => '#',
+        ('#', (_gr, tr, ly)) if ly >= 1 && tr >= 1 => '#',
+        ('#', (_gr, _tr, _ly)) => '.',
+        _ => c,
+    }
+}
+
+fn simulate(this: Grid) -> Grid {
+    Grid::from_fn(this.rows(), this.cols(), |pos| mutate(&this, pos))
+}
+
+fn iterate(this: Grid, k: usize) -> Grid {
+    let mut seen: HashMap<Grid, usize> = HashMap::new();
+    seen.insert(this.clone(), 0);
+
+    let mut next = this;
+    let mut i = 0;
+    while i < k {
+        //println!("iteration: {}", i);
+
+        let temp = simulate(next);
+        next = temp;
+
+        if seen.contains_key(&next) {
+            let p = *seen.get(&next).unwrap();
+            //println!("loop detected: i={} p={}", i, p);
+            let period = i - p;
+
+            let full_periods = (k - i + 1) / period;
+            i += full_periods * period;
+        } else {
+            seen.insert(next.clone(), i);
+        }
+
+        i += 1;
+    }
+    next
+}
+
+// Trees times lumberyards.
+fn value(grid: &Grid) -> usize {
+    let tr = grid.count(|c| *c == '|');
+    let ly = grid.count(|c| *c == '#');
+    tr * ly
+}
+
+impl Solution for Day18 {
+    type Input = Grid;
+
+    fn parse(input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(Grid::parse(input, |c| c)?)
+    }
+
+    fn part1(grid: &Self::Input) -> String {
+        value(&iterate(grid.clone(), 10)).to_string()
+    }
+
+    fn part2(grid: &Self::Input) -> String {
+        value(&iterate(grid.clone(), 1000000000)).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_grid(lines: Vec<String>) -> Grid {
+        Grid::parse(&lines.join("\n"), |c| c).unwrap()
+    }
+
+    fn wrap(lines: Vec<&'static str>) -> Vec<String> {
+        lines.into_iter().map(String::from).collect()
+    }
+
+    struct Data {
+        s0: Vec<String>,
+        s1: Vec<String>,
+    }
+
+    fn make_data() -> Data {
+        Data {
+            s0: wrap(vec![
+                ".#.#...|#.",
+                ".....#|##|",
+                ".|..|...#.",
+                "..|#.....#",
+                "#.#|||#|#|",
+                "...#.||...",
+                ".|....|...",
+                "||...#|.#|",
+                "|.||||..|.",
+                "...#.|..|.",
+            ]),
+            s1: wrap(vec![
+                ".......##.",
+                "......|###",
+                ".|..|...#.",
+                "..|#||...#",
+                "..##||.|#|",
+                "...#||||..",
+                "||...|||..",
+                "|||||.||.|",
+                "||||||||||",
+                "....||..|.",
+            ]),
+        }
+    }
+
+    #[test]
+    fn test_iter1() {
+        let data = make_data();
+        let grid = make_grid(data.s0);
+        let next = simulate(grid);
+        assert_eq!(next.dump(), data.s1);
+    }
+}
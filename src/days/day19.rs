@@ -0,0 +1,82 @@
+use std::error::Error;
+use std::io::{self, Write};
+
+use super::{Logs, Solution};
+use crate::elfcode::debug::Debugger;
+use crate::elfcode::{self, Machine, Program};
+
+pub struct Day19;
+
+type State = Machine<6>;
+
+// Executed steps of the compiled program are written to `trace` when given.
+fn process(program: &Program, state: State, trace: Option<&mut dyn Write>) -> io::Result<State> {
+    let mut dbg = Debugger::new(&program.codes, state);
+    dbg.optimize();
+    if let Some(sink) = trace {
+        dbg.trace(sink);
+    }
+    dbg.run()?;
+    Ok(dbg.machine)
+}
+
+impl Solution for Day19 {
+    type Input = Program;
+
+    fn parse(input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(elfcode::parse(input, 6)?)
+    }
+
+    fn part1(input: &Self::Input) -> String {
+        Self::part1_logged(input, &mut Logs::default()).unwrap()
+    }
+
+    fn part1_logged(input: &Self::Input, logs: &mut Logs) -> io::Result<String> {
+        let zero = State::new(input.ip, [0, 0, 0, 0, 0, 0]);
+        Ok(process(input, zero, logs.trace_sink())?.get(0).to_string())
+    }
+
+    // Naive simulation is pointless as it takes > 10^14 steps.
+    // The program is actually summing dividers of a 10551376:
+    //
+    // c = 10551376
+    // for b in 1..=c
+    //   for f in 1..=c
+    //     if b * f == c
+    //       a = a + b;
+    //
+    // Compiled program runs both loops as a single instruction.
+    fn part2(input: &Self::Input) -> String {
+        Self::part2_logged(input, &mut Logs::default()).unwrap()
+    }
+
+    fn part2_logged(input: &Self::Input, logs: &mut Logs) -> io::Result<String> {
+        let zero = State::new(input.ip, [1, 0, 0, 0, 0, 0]);
+        Ok(process(input, zero, logs.trace_sink())?.get(0).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_program() {
+        let program = Day19::parse(
+            &[
+                "#ip 0",
+                "seti 5 0 1",
+                "seti 6 0 2",
+                "addi 0 1 0",
+                "addr 1 2 3",
+                "setr 1 0 0",
+                "seti 8 0 4",
+                "seti 9 0 5",
+            ]
+            .join("\n"),
+        )
+        .unwrap();
+
+        assert_eq!(Day19::part1(&program), "6");
+    }
+}
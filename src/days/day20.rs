@@ -0,0 +1,692 @@
+use std::error::Error;
+
+use super::Solution;
+use crate::{geom, grid, search};
+
+pub struct Day20;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+struct Pos {
+    x: isize,
+    y: isize,
+}
+
+impl Pos {
+    fn zero() -> Pos {
+        Pos { x: 0, y: 0 }
+    }
+
+    fn step(&self, dir: char) -> Pos {
+        match dir {
+            'N' => Pos {
+                y: self.y - 2,
+                ..*self
+            },
+            'S' => Pos {
+                y: self.y + 2,
+                ..*self
+            },
+            'E' => Pos {
+                x: self.x + 2,
+                ..*self
+            },
+            'W' => Pos {
+                x: self.x - 2,
+                ..*self
+            },
+            _ => *self,
+        }
+    }
+
+    fn back(&self, dir: char) -> Pos {
+        match dir {
+            'N' => Pos {
+                y: self.y + 1,
+                ..*self
+            },
+            'S' => Pos {
+                y: self.y - 1,
+                ..*self
+            },
+            'E' => Pos {
+                x: self.x - 1,
+                ..*self
+            },
+            'W' => Pos {
+                x: self.x + 1,
+                ..*self
+            },
+            _ => *self,
+        }
+    }
+}
+
+fn traverse_rec(rec: &Rec, at: Pos) -> Vec<(char, Pos)> {
+    let mut acc = Vec::new();
+    acc.push(('X', at));
+    traverse_rec_tree(rec, &[at], &mut acc);
+    acc
+}
+
+fn traverse_rec_tree(rec: &Rec, from: &[Pos], acc: &mut Vec<(char, Pos)>) -> Vec<Pos> {
+    let mut last: Vec<Pos> = Vec::new();
+
+    if !rec.leaf.is_empty() {
+        for f in from {
+            let mut p = *f;
+            for c in &rec.leaf {
+                let n = p.step(*c);
+                acc.push((*c, n));
+                p = n;
+            }
+            last.push(p);
+        }
+    } else if !rec.list.is_empty() {
+        last = from.to_vec();
+        for r in &rec.list {
+            let next = traverse_rec_tree(r, &last, acc);
+            last = next;
+        }
+    } else if !rec.fork.is_empty() {
+        for r in &rec.fork {
+            let mut next = traverse_rec_tree(r, from, acc);
+            last.append(&mut next);
+        }
+    }
+
+    last
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct Rec {
+    leaf: Vec<char>,
+    fork: Vec<Rec>,
+    list: Vec<Rec>,
+}
+
+impl Rec {
+    fn new() -> Rec {
+        Rec {
+            leaf: vec![],
+            fork: vec![],
+            list: vec![],
+        }
+    }
+
+    #[cfg(test)]
+    fn leaf(leaf: Vec<char>) -> Rec {
+        Rec { leaf, ..Rec::new() }
+    }
+
+    #[cfg(test)]
+    fn fork(fork: Vec<Rec>) -> Rec {
+        Rec { fork, ..Rec::new() }
+    }
+
+    #[cfg(test)]
+    fn list(list: Vec<Rec>) -> Rec {
+        Rec { list, ..Rec::new() }
+    }
+}
+
+// One line per node, children indented under their parent.
+#[cfg(test)]
+fn print_rec_tree(rec: &Rec) -> Vec<String> {
+    fn walk(rec: &Rec, depth: usize, acc: &mut Vec<String>) {
+        let pad = "  ".repeat(depth);
+        if !rec.list.is_empty() {
+            acc.push(format!("{}list", pad));
+            rec.list.iter().for_each(|r| walk(r, depth + 1, acc));
+        } else if !rec.fork.is_empty() {
+            acc.push(format!("{}fork", pad));
+            rec.fork.iter().for_each(|r| walk(r, depth + 1, acc));
+        } else {
+            acc.push(format!("{}{}", pad, rec.leaf.iter().collect::<String>()));
+        }
+    }
+    let mut acc = Vec::new();
+    walk(rec, 0, &mut acc);
+    acc
+}
+
+fn reduce_list(rec: Rec) -> Rec {
+    if rec.list.len() == 1 {
+        rec.list[0].to_owned()
+    } else {
+        rec
+    }
+}
+
+fn reduce_fork(rec: Rec) -> Rec {
+    if rec.fork.len() == 1 {
+        rec.fork[0].to_owned()
+    } else {
+        rec
+    }
+}
+
+fn fetch_leaf(chars: &[char], at: usize) -> (Rec, usize) {
+    let mut rec: Rec = Rec::new();
+    let mut i = at;
+    loop {
+        let c = chars[i];
+        //println!("leaf: i={} c={} rec={:?}", i, c, rec);
+        match c {
+            'N' | 'S' | 'E' | 'W' => rec.leaf.push(c),
+            _ => break,
+        }
+        i += 1;
+    }
+    (rec, i)
+}
+
+fn fetch_list(chars: &[char], at: usize) -> (Rec, usize) {
+    let mut rec: Rec = Rec::new();
+    let mut i = at;
+    loop {
+        let c = chars[i];
+        //println!("list: i={} c={} rec={:?}", i, c, rec);
+        match c {
+            'N' | 'S' | 'E' | 'W' => {
+                let (r, j) = fetch_leaf(chars, i);
+                rec.list.push(r);
+                i = j;
+            }
+            '(' => {
+                let (f, j) = fetch_fork(chars, i + 1);
+                rec.list.push(f);
+                i = j;
+            }
+            '|' => {
+                //println!("Char '|' not expected in list at {}", i);
+                //i += 1;
+                break;
+            }
+            _ => break,
+        }
+    }
+    (reduce_list(rec), i)
+}
+
+fn fetch_fork(chars: &[char], at: usize) -> (Rec, usize) {
+    let mut rec: Rec = Rec::new();
+    let mut i = at;
+
+    let mut stack: Vec<Rec> = Vec::new();
+    loop {
+        if i >= chars.len() {
+            break;
+        }
+        let c = chars[i];
+        //println!("fork: i={} c={} rec={:?}", i, c, rec);
+        match c {
+            'N' | 'S' | 'W' | 'E' => {
+                let (r, j) = fetch_list(chars, i);
+                rec.fork.push(r);
+                i = j;
+            }
+            '|' => {
+                let (l, j) = fetch_list(chars, i + 1);
+                rec.fork.push(l);
+                i = j;
+            }
+            '(' => {
+                stack.push(rec);
+                rec = Rec::new();
+                let (f, j) = fetch_fork(chars, i + 1);
+                rec.fork.push(f);
+                i = j;
+            }
+            ')' => {
+                if !stack.is_empty() {
+                    rec = stack.pop().unwrap();
+                    i += 1;
+                } else {
+                    i += 1;
+                    break;
+                }
+            }
+            _ => break,
+        }
+    }
+    //println!("\tfork: rec={:?}", rec);
+    (reduce_fork(rec), i)
+}
+
+fn fetch_tree(chars: &[char]) -> Rec {
+    let (rec, _) = fetch_list(chars, 1); // skip '^' at index 0
+    rec
+}
+
+#[derive(Debug)]
+struct Size {
+    minx: isize,
+    miny: isize,
+    width: usize,
+    height: usize,
+}
+
+impl Size {
+    fn at(&self, p: Pos) -> Pos {
+        Pos {
+            x: p.x - self.minx,
+            y: p.y - self.miny,
+        }
+    }
+}
+
+fn get_size(steps: &[(char, Pos)]) -> Size {
+    fn max(a: isize, b: isize) -> isize {
+        if a >= b {
+            a
+        } else {
+            b
+        }
+    }
+    fn min(a: isize, b: isize) -> isize {
+        if a <= b {
+            a
+        } else {
+            b
+        }
+    }
+
+    let (mut minx, mut miny, mut maxx, mut maxy) = (isize::MAX, isize::MAX, isize::MIN, isize::MIN);
+
+    for s in steps {
+        let (_, p) = s;
+        minx = min(minx, p.x);
+        maxx = max(maxx, p.x);
+        miny = min(miny, p.y);
+        maxy = max(maxy, p.y);
+    }
+
+    Size {
+        minx: minx - 1,
+        miny: miny - 1,
+        width: ((maxx - minx).abs() + 3) as usize,
+        height: ((maxy - miny).abs() + 3) as usize,
+    }
+}
+
+type Grid = grid::Grid<char>;
+
+// Grid cell of a (shifted, non-negative) plane position.
+fn cell(p: Pos) -> geom::Pos {
+    geom::Pos::new(p.y as usize, p.x as usize)
+}
+
+fn make_grid(size: &Size, steps: &[(char, Pos)]) -> Grid {
+    let mut grid = Grid::new(size.height, size.width, '#');
+    for s in steps {
+        let (d, p) = s;
+        grid[cell(size.at(*p))] = '.';
+        let c = match *d {
+            'N' | 'S' => '-',
+            'E' | 'W' => '|',
+            x => x,
+        };
+        grid[cell(size.at(p.back(*d)))] = c;
+    }
+    grid
+}
+
+// Distances from the given room, going through the doors.
+fn bfs(grid: &Grid, at: Pos) -> grid::Grid<usize> {
+    let mut doors = |p: geom::Pos, out: &mut Vec<(geom::Pos, usize)>| {
+        for &(dr, dc) in [(-1, 0), (0, -1), (0, 1), (1, 0)].iter() {
+            let door = p.offset(dr, dc).and_then(|d| grid.get(d));
+            if let Some('-') | Some('|') = door {
+                out.extend(p.offset(2 * dr, 2 * dc).map(|room| (room, 1)));
+            }
+        }
+    };
+    let visits = grid::Grid::new(grid.rows(), grid.cols(), None);
+    let search = search::bfs(&mut doors, &[cell(at)], visits, |_| false);
+    grid::Grid::from_fn(grid.rows(), grid.cols(), |p| {
+        search.dist(p).unwrap_or(usize::MAX)
+    })
+}
+
+// Distances to all rooms.
+fn rooms<'a>(grid: &'a Grid, dist: &'a grid::Grid<usize>) -> impl Iterator<Item = usize> + 'a {
+    grid.iter()
+        .filter(|(_, c)| **c == '.')
+        .map(move |(p, _)| dist[p])
+}
+
+fn max(grid: &Grid, dist: &grid::Grid<usize>) -> usize {
+    rooms(grid, dist).max().unwrap_or(0)
+}
+
+fn count<F>(grid: &Grid, dist: &grid::Grid<usize>, f: F) -> usize
+where
+    F: Fn(usize) -> bool,
+{
+    rooms(grid, dist).filter(|d| f(*d)).count()
+}
+
+// Map of the facility with distances from the starting room.
+fn explore(input: &str) -> (Grid, grid::Grid<usize>) {
+    let rec = fetch_tree(&input.chars().collect::<Vec<_>>());
+    let cells = traverse_rec(&rec, Pos::zero());
+    let size = get_size(&cells);
+    let grid = make_grid(&size, &cells);
+    let dist = bfs(&grid, size.at(Pos::zero()));
+    (grid, dist)
+}
+
+impl Solution for Day20 {
+    type Input = (Grid, grid::Grid<usize>);
+
+    fn parse(input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        let input = input.trim();
+        if !input.starts_with('^') || !input.ends_with('$') {
+            return Err("expected a regex in '^...$'".into());
+        }
+        let mut depth = 0;
+        for c in input[1..input.len() - 1].chars() {
+            match c {
+                'N' | 'S' | 'E' | 'W' | '|' => (),
+                '(' => depth += 1,
+                ')' if depth > 0 => depth -= 1,
+                _ => return Err(format!("unexpected '{}' in the regex", c).into()),
+            }
+        }
+        if depth != 0 {
+            return Err("unbalanced parentheses in the regex".into());
+        }
+        Ok(explore(input))
+    }
+
+    // Furthest room.
+    fn part1((grid, dist): &Self::Input) -> String {
+        max(grid, dist).to_string()
+    }
+
+    // Rooms at least 1000 doors away.
+    fn part2((grid, dist): &Self::Input) -> String {
+        count(grid, dist, |x| x >= 1000).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solve(input: &str) -> (Grid, usize, usize) {
+        let (grid, dist) = explore(input);
+        let d = max(&grid, &dist);
+        let n = count(&grid, &dist, |x| x >= 1000);
+        (grid, d, n)
+    }
+
+    fn wrap(lines: Vec<&'static str>) -> Vec<String> {
+        lines.into_iter().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_fetch_tree() {
+        let input: Vec<char> = "^NE(W|E(N|S))WS$".chars().collect();
+        println!("input: {:?}", input);
+        let exp = Rec::list(vec![
+            Rec::leaf(vec!['N', 'E']),
+            Rec::fork(vec![
+                Rec::leaf(vec!['W']),
+                Rec::list(vec![
+                    Rec::leaf(vec!['E']),
+                    Rec::fork(vec![Rec::leaf(vec!['N']), Rec::leaf(vec!['S'])]),
+                ]),
+            ]),
+            Rec::leaf(vec!['W', 'S']),
+        ]);
+        // println!("exp:\n{}", print_rec_tree(&exp).join("\n"));
+
+        let rec = fetch_tree(&input);
+        println!("rec:\n{}", print_rec_tree(&rec).join("\n"));
+        println!("exp:\n{}", print_rec_tree(&exp).join("\n"));
+
+        assert_eq!(rec, exp);
+    }
+
+    fn parse_tree(input: &str) -> Rec {
+        let chars: Vec<char> = input.chars().collect();
+        fetch_tree(&chars)
+    }
+
+    #[test]
+    fn test_parse_tree1() {
+        assert_eq!(
+            parse_tree("^NN(EE|WW)SS$"),
+            Rec::list(vec![
+                Rec::leaf(vec!['N', 'N']),
+                Rec::fork(vec![Rec::leaf(vec!['E', 'E']), Rec::leaf(vec!['W', 'W'])]),
+                Rec::leaf(vec!['S', 'S']),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_tree2() {
+        assert_eq!(
+            parse_tree("^NEWS(SWEN|EWNS)$"),
+            Rec::list(vec![
+                Rec::leaf(vec!['N', 'E', 'W', 'S']),
+                Rec::fork(vec![
+                    Rec::leaf(vec!['S', 'W', 'E', 'N']),
+                    Rec::leaf(vec!['E', 'W', 'N', 'S']),
+                ]),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_tree3_1() {
+        assert_eq!(
+            parse_tree("^NN(E|NEWS)SS$"),
+            Rec::list(vec![
+                Rec::leaf(vec!['N', 'N']),
+                Rec::fork(vec![
+                    Rec::leaf(vec!['E']),
+                    Rec::leaf(vec!['N', 'E', 'W', 'S']),
+                ]),
+                Rec::leaf(vec!['S', 'S']),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_tree3_2() {
+        assert_eq!(
+            parse_tree("^NN(E|NEWS|S)SS$"),
+            Rec::list(vec![
+                Rec::leaf(vec!['N', 'N']),
+                Rec::fork(vec![
+                    Rec::leaf(vec!['E']),
+                    Rec::leaf(vec!['N', 'E', 'W', 'S']),
+                    Rec::leaf(vec!['S']),
+                ]),
+                Rec::leaf(vec!['S', 'S']),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_tree3_3() {
+        assert_eq!(
+            parse_tree("^NN(E|NE(W|E)WS|S)SS$"),
+            Rec::list(vec![
+                Rec::leaf(vec!['N', 'N']),
+                Rec::fork(vec![
+                    Rec::leaf(vec!['E']),
+                    Rec::list(vec![
+                        Rec::leaf(vec!['N', 'E']),
+                        Rec::fork(vec![Rec::leaf(vec!['W']), Rec::leaf(vec!['E'])]),
+                        Rec::leaf(vec!['W', 'S']),
+                    ]),
+                    Rec::leaf(vec!['S']),
+                ]),
+                Rec::leaf(vec!['S', 'S']),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_traverse() {
+        let z = Pos::zero();
+        let ps = traverse_rec(&parse_tree("^NN(E|W)SS$"), z);
+        assert_eq!(
+            ps,
+            vec![
+                ('X', z),
+                ('N', z.step('N')),
+                ('N', z.step('N').step('N')),
+                ('E', z.step('N').step('N').step('E')),
+                ('W', z.step('N').step('N').step('W')),
+                ('S', z.step('N').step('N').step('E').step('S')),
+                ('S', z.step('N').step('N').step('E').step('S').step('S')),
+                ('S', z.step('N').step('N').step('W').step('S')),
+                ('S', z.step('N').step('N').step('W').step('S').step('S')),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_traverse_rec() {
+        // NN(E|W)SS
+        let rec = Rec::list(vec![
+            Rec::leaf(vec!['N', 'N']),
+            Rec::fork(vec![Rec::leaf(vec!['E']), Rec::leaf(vec!['W'])]),
+            Rec::leaf(vec!['S', 'S']),
+        ]);
+
+        let z = Pos::zero();
+        let ps = traverse_rec(&rec, z);
+        assert_eq!(
+            ps,
+            vec![
+                ('X', z),
+                ('N', z.step('N')),
+                ('N', z.step('N').step('N')),
+                ('E', z.step('N').step('N').step('E')),
+                ('W', z.step('N').step('N').step('W')),
+                ('S', z.step('N').step('N').step('E').step('S')),
+                ('S', z.step('N').step('N').step('E').step('S').step('S')),
+                ('S', z.step('N').step('N').step('W').step('S')),
+                ('S', z.step('N').step('N').step('W').step('S').step('S')),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_solve1() {
+        let input = "^ENNWSWW(NEWS|)SSSEEN(WNSE|)EE(SWEN|)NNN$".to_string();
+        let (grid, d, _) = solve(&input);
+        assert_eq!(d, 18);
+        assert_eq!(
+            grid.dump(),
+            wrap(vec![
+                "###########",
+                "#.|.#.|.#.#",
+                "#-###-#-#-#",
+                "#.|.|.#.#.#",
+                "#-#####-#-#",
+                "#.#.#X|.#.#",
+                "#-#-#####-#",
+                "#.#.|.|.|.#",
+                "#-###-###-#",
+                "#.|.|.#.|.#",
+                "###########",
+            ])
+        );
+    }
+
+    #[test]
+    fn test_solve2_fetch_tree() {
+        let input: Vec<char> = "^ESSWWN(E|NNENN(EESS(WNSE|)SSS|WWWSSSSE(SW|NNNE)))$"
+            .chars()
+            .collect();
+        println!("{:?}", input);
+        let rec = fetch_tree(&input);
+        let exp = Rec::list(vec![
+            Rec::leaf(vec!['E', 'S', 'S', 'W', 'W', 'N']),
+            Rec::fork(vec![
+                Rec::leaf(vec!['E']),
+                Rec::list(vec![
+                    Rec::leaf(vec!['N', 'N', 'E', 'N', 'N']),
+                    Rec::fork(vec![
+                        Rec::list(vec![
+                            Rec::leaf(vec!['E', 'E', 'S', 'S']),
+                            Rec::fork(vec![Rec::leaf(vec!['W', 'N', 'S', 'E']), Rec::leaf(vec![])]),
+                            Rec::leaf(vec!['S', 'S', 'S']),
+                        ]),
+                        Rec::list(vec![
+                            Rec::leaf(vec!['W', 'W', 'W', 'S', 'S', 'S', 'S', 'E']),
+                            Rec::fork(vec![
+                                Rec::leaf(vec!['S', 'W']),
+                                Rec::leaf(vec!['N', 'N', 'N', 'E']),
+                            ]),
+                        ]),
+                    ]),
+                ]),
+            ]),
+        ]);
+        println!("\nrec:\n{}", print_rec_tree(&rec).join("\n"));
+        println!("\nexp:\n{}", print_rec_tree(&exp).join("\n"));
+
+        assert_eq!(rec, exp);
+        assert_eq!(print_rec_tree(&rec), print_rec_tree(&exp));
+    }
+
+    #[test]
+    fn test_solve2() {
+        let input = "^ESSWWN(E|NNENN(EESS(WNSE|)SSS|WWWSSSSE(SW|NNNE)))$".to_string();
+        let (grid, d, _) = solve(&input);
+        assert_eq!(
+            grid.dump(),
+            wrap(vec![
+                "#############",
+                "#.|.|.|.|.|.#",
+                "#-#####-###-#",
+                "#.#.|.#.#.#.#",
+                "#-#-###-#-#-#",
+                "#.#.#.|.#.|.#",
+                "#-#-#-#####-#",
+                "#.#.#.#X|.#.#",
+                "#-#-#-###-#-#",
+                "#.|.#.|.#.#.#",
+                "###-#-###-#-#",
+                "#.|.#.|.|.#.#",
+                "#############",
+            ])
+        );
+        assert_eq!(d, 23);
+    }
+
+    #[test]
+    fn test_solve3() {
+        let input = "^WSSEESWWWNW(S|NENNEEEENN(ESSSSW(NWSW|SSEN)|WSWWN(E|WWS(E|SS))))$".to_string();
+        let (grid, d, _) = solve(&input);
+        assert_eq!(
+            grid.dump(),
+            wrap(vec![
+                "###############",
+                "#.|.|.|.#.|.|.#",
+                "#-###-###-#-#-#",
+                "#.|.#.|.|.#.#.#",
+                "#-#########-#-#",
+                "#.#.|.|.|.|.#.#",
+                "#-#-#########-#",
+                "#.#.#.|X#.|.#.#",
+                "###-#-###-#-#-#",
+                "#.|.#.#.|.#.|.#",
+                "#-###-#####-###",
+                "#.|.#.|.|.#.#.#",
+                "#-#-#####-#-#-#",
+                "#.#.|.|.|.#.|.#",
+                "###############",
+            ])
+        );
+        assert_eq!(d, 31);
+    }
+}
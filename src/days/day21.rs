@@ -0,0 +1,186 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::io::{self, Write};
+
+use super::{Logs, Solution};
+use crate::elfcode::cfg::Cfg;
+use crate::elfcode::debug::{Debugger, Stop};
+use crate::elfcode::flow::{flow, Flow};
+use crate::elfcode::{self, Machine, Opcode, Program};
+
+pub struct Day21;
+
+/*
+
+#ip 4
+00: seti 123 0 2        # r[2] = 123;
+01: bani 2 456 2        # r[2] = r[2] & 456;
+02: eqri 2 72 2         # if r[2] == 72 { r[2] = 1; } else { r[2] = 0; }
+03: addr 2 4 4          # r[4] = r[2] + r[4]; // when r[2] == 1, jump over line 04
+04: seti 0 0 4          # // r[4] = 0; // executed only if r[2] was 0
+05: seti 0 0 2          # r[2] = 0;
+06: bori 2 65536 5      # r[5] = r[2] | 65536;
+07: seti 5234604 6 2    # r[2] = 5234604;
+
+08: bani 5 255 3        # r[3] = r[5] & 255;
+09: addr 2 3 2          # r[2] = r[2] + r[3];
+10: bani 2 16777215 2   # r[2] = r[2] & 16777215;
+11: muli 2 65899 2      # r[2] = r[2] * 65899;
+12: bani 2 16777215 2   # r[2] = r[2] & 16777215; // r[2] = 344955168996, r[5] = 65536,
+13: gtir 256 5 3        # if 256 > r[5] { r[3] = 1; } else { r[3] = 0; };
+14: addr 3 4 4          # r[4] = r[4] + r[3]; // r[3] = 0
+15: addi 4 1 4          # r[4] = r[4] + 1;
+16: seti 27 2 4         # // r[4] = 27; // jump to 28
+17: seti 0 0 3          # r[3] = 0;
+
+18: addi 3 1 1          # r[1] = r[3] + 1;
+19: muli 1 256 1        # r[1] = r[1] * 256;
+20: gtrr 1 5 1          # if r[1] > r[5] { r[1] = 1 } else { r[1] = 0 }
+21: addr 1 4 4          # r[4] = r[1] + r[4];
+22: addi 4 1 4          # r[4] = r[4] + 1;
+23: seti 25 6 4         # // r[4] = 25; // jump to line 26
+24: addi 3 1 3          # r[3] = r[3] + 1;
+25: seti 17 7 4         # r[4] = 14; // jump to line 18
+
+26: setr 3 4 5          # r[5] = r[3];
+27: seti 7 8 4          # r[4] = 7; // jump to line 8
+
+28: eqrr 2 0 3          # if r[2] == r[0] { r[3] = 1; } else { r[3] = 0; }
+29: addr 3 4 4          # r[4] = r[3] + r[4]; // halt: jump to 31
+30: seti 5 6 4          # r[5] = 5; // jump to line 6
+
+*/
+
+type State = Machine<6>;
+
+// The only way out of the program is a branch on comparing some register
+// against r[0]: returns index of that comparison and the register compared.
+fn find_check(program: &Program) -> Option<(usize, usize)> {
+    let cfg = Cfg::build(program);
+    cfg.blocks
+        .iter()
+        .filter(|b| b.exit && b.end >= 2)
+        .filter(|b| matches!(flow(program, b.end - 1), Flow::Branch { .. }))
+        .map(|b| (b.end - 2, &program.codes[b.end - 2]))
+        .find_map(|(at, code)| match (code.a, code.b) {
+            (0, r) | (r, 0) if code.op == Opcode::Eqrr => Some((at, r)),
+            _ => None,
+        })
+}
+
+// Values r[0] is compared against, in order, until they start repeating
+// or there are `max` of them. Executed steps of the compiled program are
+// written to `trace` when given.
+fn checks(program: &Program, max: usize, trace: Option<&mut dyn Write>) -> io::Result<Vec<usize>> {
+    let state = State::new(program.ip, [0, 0, 0, 0, 0, 0]);
+    let mut dbg = Debugger::new(&program.codes, state);
+    dbg.optimize();
+    if let Some(sink) = trace {
+        dbg.trace(sink);
+    }
+
+    let reg = match find_check(program) {
+        Some((at, reg)) => {
+            dbg.breakpoint(at);
+            reg
+        }
+        None => return Ok(vec![]),
+    };
+
+    let mut seen = HashSet::new();
+    let mut result = Vec::new();
+    while result.len() < max {
+        if !matches!(dbg.run()?, Stop::Breakpoint(_)) {
+            break;
+        }
+        let val = dbg.machine.get(reg);
+        if !seen.insert(val) {
+            break;
+        }
+        result.push(val);
+    }
+    Ok(result)
+}
+
+impl Solution for Day21 {
+    type Input = Program;
+
+    fn parse(input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(elfcode::parse(input, 6)?)
+    }
+
+    // Fewest instructions: halt on the very first check.
+    fn part1(input: &Self::Input) -> String {
+        Self::part1_logged(input, &mut Logs::default()).unwrap()
+    }
+
+    fn part1_logged(input: &Self::Input, logs: &mut Logs) -> io::Result<String> {
+        Ok(match checks(input, 1, logs.trace_sink())?.first() {
+            Some(val) => val.to_string(),
+            None => "none".to_string(),
+        })
+    }
+
+    // Most instructions: halt on the last check before the values cycle.
+    fn part2(input: &Self::Input) -> String {
+        Self::part2_logged(input, &mut Logs::default()).unwrap()
+    }
+
+    fn part2_logged(input: &Self::Input, logs: &mut Logs) -> io::Result<String> {
+        Ok(match checks(input, usize::MAX, logs.trace_sink())?.last() {
+            Some(val) => val.to_string(),
+            None => "none".to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_program() {
+        let program = Day21::parse(
+            &[
+                "#ip 0",
+                "seti 5 0 1",
+                "seti 6 0 2",
+                "addi 0 1 0",
+                "addr 1 2 3",
+                "setr 1 0 0",
+                "seti 8 0 4",
+                "seti 9 0 5",
+            ]
+            .join("\n"),
+        )
+        .unwrap();
+
+        let mut state = State::new(program.ip, [0, 0, 0, 0, 0, 0]);
+        state.run(&program.codes);
+        assert_eq!(state.get(0), 6);
+
+        // No exit check on r[0]: the program just runs to completion.
+        assert_eq!(find_check(&program), None);
+        assert_eq!(Day21::part1(&program), "none");
+    }
+
+    #[test]
+    fn test_find_check() {
+        // Loops comparing r[2] against r[0], leaving when they're equal.
+        let program = Day21::parse(
+            &[
+                "#ip 4",
+                "seti 7 0 2",
+                "eqrr 2 0 1",
+                "addr 1 4 4",
+                "seti 0 0 4",
+            ]
+            .join("\n"),
+        )
+        .unwrap();
+
+        assert_eq!(find_check(&program), Some((1, 2)));
+        assert_eq!(checks(&program, usize::MAX, None).unwrap(), vec![7]);
+        assert_eq!(Day21::part1(&program), "7");
+    }
+}
@@ -0,0 +1,409 @@
+use std::error::Error;
+use std::fmt;
+
+use super::Solution;
+use crate::search::{self, Graph, Visit, Visits};
+
+pub struct Day22;
+
+// Cave depth and target coordinates, as in:
+//
+// depth: 510
+// target: 10,10
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct Scan {
+    depth: u64,
+    target: (usize, usize),
+}
+
+fn parse_scan(input: &str) -> Result<Scan, String> {
+    let lines = crate::input::lines(input);
+    let value = |i: usize, key: &str| -> Result<&str, String> {
+        let line = lines.get(i).map(|l| l.trim()).unwrap_or("");
+        line.strip_prefix(key)
+            .map(str::trim)
+            .ok_or_else(|| format!("line {}: expected '{}', found '{}'", i + 1, key, line))
+    };
+    let depth = value(0, "depth:")?;
+    let depth = depth
+        .parse()
+        .map_err(|_| format!("line 1: invalid depth '{}'", depth))?;
+    let target = value(1, "target:")?;
+    let (x, y) = target
+        .split_once(',')
+        .and_then(|(x, y)| Some((x.trim().parse().ok()?, y.trim().parse().ok()?)))
+        .ok_or_else(|| format!("line 2: invalid target '{}'", target))?;
+    Ok(Scan {
+        depth,
+        target: (x, y),
+    })
+}
+
+impl Solution for Day22 {
+    type Input = Scan;
+
+    fn parse(input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(parse_scan(input)?)
+    }
+
+    // Total risk level of the rectangle up to the target.
+    fn part1(scan: &Self::Input) -> String {
+        let mut grid = Grid::new(*scan);
+        sum(&mut grid).to_string()
+    }
+
+    // Fewest minutes to reach the target with the torch.
+    fn part2(scan: &Self::Input) -> String {
+        let mut grid = Grid::new(*scan);
+        grid.route().time.to_string()
+    }
+}
+
+const MODULO: u64 = 20183;
+
+// Cave with region types generated on demand. Erosion levels are kept in a dense
+// table (rows are y, columns are x) grown to cover every region asked for.
+pub struct Grid {
+    depth: u64,
+    target: (usize, usize),
+    erosion: Vec<Vec<u64>>,
+}
+
+impl Grid {
+    pub fn new(scan: Scan) -> Grid {
+        Grid {
+            depth: scan.depth,
+            target: scan.target,
+            erosion: Vec::new(),
+        }
+    }
+
+    // Cave from the puzzle input: "depth: N" and "target: X,Y" lines.
+    pub fn parse(input: &str) -> Result<Grid, String> {
+        parse_scan(input).map(Grid::new)
+    }
+
+    fn width(&self) -> usize {
+        self.erosion.first().map(|row| row.len()).unwrap_or(0)
+    }
+
+    // Grows the table (at least doubling it) to contain (x, y).
+    fn ensure(&mut self, x: usize, y: usize) {
+        let (w, h) = (self.width(), self.erosion.len());
+        if x < w && y < h {
+            return;
+        }
+        let w = if x < w { w } else { (x + 1).max(2 * w) };
+        let h = if y < h { h } else { (y + 1).max(2 * h) };
+        for row in 0..h {
+            if row == self.erosion.len() {
+                self.erosion.push(Vec::with_capacity(w));
+            }
+            for col in self.erosion[row].len()..w {
+                let geo = match (col, row) {
+                    (0, 0) => 0,
+                    at if at == self.target => 0,
+                    (x, 0) => x as u64 * 16807,
+                    (0, y) => y as u64 * 48271,
+                    _ => self.erosion[row][col - 1] * self.erosion[row - 1][col],
+                };
+                self.erosion[row].push((geo + self.depth) % MODULO);
+            }
+        }
+    }
+
+    // Region type: '.' rocky, '=' wet or '|' narrow.
+    pub fn cell(&mut self, x: usize, y: usize) -> char {
+        self.ensure(x, y);
+        match self.erosion[y][x] % 3 {
+            0 => '.',
+            1 => '=',
+            _ => '|',
+        }
+    }
+
+    // Region types of the rectangle between the corners (inclusive), one line per y,
+    // with the mouth of the cave as 'M' and the target as 'T'.
+    pub fn render(&mut self, from: (usize, usize), to: (usize, usize)) -> Vec<String> {
+        (from.1..=to.1)
+            .map(|y| {
+                (from.0..=to.0)
+                    .map(|x| match (x, y) {
+                        (0, 0) => 'M',
+                        at if at == self.target => 'T',
+                        _ => self.cell(x, y),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Copy, Clone, Hash)]
+pub enum Tool {
+    Neither,
+    Gear,
+    Torch,
+}
+
+impl Tool {
+    const ALL: [Tool; 3] = [Tool::Neither, Tool::Gear, Tool::Torch];
+
+    // Rocky: gear or torch, wet: gear or neither, narrow: torch or neither.
+    fn fits(self, region: char) -> bool {
+        !matches!(
+            (region, self),
+            ('.', Tool::Neither) | ('=', Tool::Torch) | ('|', Tool::Gear)
+        )
+    }
+}
+
+impl fmt::Display for Tool {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Tool::Neither => write!(f, "neither"),
+            Tool::Gear => write!(f, "climbing gear"),
+            Tool::Torch => write!(f, "torch"),
+        }
+    }
+}
+
+const MOVE: usize = 1;
+const SWITCH: usize = 7;
+
+// Where the rescuer is and what it's holding.
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Copy, Clone, Hash)]
+pub struct State {
+    pub pos: (usize, usize),
+    pub tool: Tool,
+}
+
+// Visits stored densely (y, then x, then tool), rows grow on demand,
+// so memory follows the explored part of the cave.
+#[derive(Default)]
+struct Table {
+    rows: Vec<Vec<[Option<Visit<State>>; 3]>>,
+}
+
+impl Visits<State> for Table {
+    fn get(&self, state: State) -> Option<Visit<State>> {
+        let (x, y) = state.pos;
+        self.rows
+            .get(y)
+            .and_then(|row| row.get(x))
+            .and_then(|cell| cell[state.tool as usize])
+    }
+
+    fn set(&mut self, state: State, visit: Visit<State>) {
+        let (x, y) = state.pos;
+        if y >= self.rows.len() {
+            self.rows.resize_with(y + 1, Vec::new);
+        }
+        let row = &mut self.rows[y];
+        if x >= row.len() {
+            row.resize(x + 1, [None; 3]);
+        }
+        row[x][state.tool as usize] = Some(visit);
+    }
+}
+
+// Fastest way to the target, state by state from the mouth of the cave.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Route {
+    pub time: usize,
+    pub states: Vec<State>,
+}
+
+impl Route {
+    // Tool switches along the route: position, old tool, new tool.
+    pub fn switches(&self) -> Vec<((usize, usize), Tool, Tool)> {
+        self.states
+            .windows(2)
+            .filter(|w| w[0].tool != w[1].tool)
+            .map(|w| (w[0].pos, w[0].tool, w[1].tool))
+            .collect()
+    }
+}
+
+impl fmt::Display for Route {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut time = 0;
+        for w in self.states.windows(2) {
+            let (a, b) = (w[0], w[1]);
+            if a.tool != b.tool {
+                time += SWITCH;
+                writeln!(
+                    f,
+                    "{:>5}: switch to {} at {},{}",
+                    time, b.tool, a.pos.0, a.pos.1
+                )?;
+            } else {
+                time += MOVE;
+                writeln!(f, "{:>5}: move to {},{}", time, b.pos.0, b.pos.1)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Graph<State> for Grid {
+    fn edges(&mut self, state: State, out: &mut Vec<(State, usize)>) {
+        let (x, y) = state.pos;
+        let region = self.cell(x, y);
+        for tool in Tool::ALL.iter() {
+            if *tool != state.tool && tool.fits(region) {
+                let next = State {
+                    pos: (x, y),
+                    tool: *tool,
+                };
+                out.push((next, SWITCH));
+            }
+        }
+        let around = [
+            y.checked_sub(1).map(|y| (x, y)),
+            x.checked_sub(1).map(|x| (x, y)),
+            Some((x + 1, y)),
+            Some((x, y + 1)),
+        ];
+        for pos in around.iter().flatten().copied() {
+            if state.tool.fits(self.cell(pos.0, pos.1)) {
+                let next = State {
+                    pos,
+                    tool: state.tool,
+                };
+                out.push((next, MOVE));
+            }
+        }
+    }
+}
+
+impl Grid {
+    // A* over (position, tool) from the mouth holding the torch to the target
+    // holding the torch. The heuristic (distance plus a switch to the torch unless
+    // already holding it) never overestimates.
+    pub fn route(&mut self) -> Route {
+        let start = State {
+            pos: (0, 0),
+            tool: Tool::Torch,
+        };
+        let goal = State {
+            pos: self.target,
+            tool: Tool::Torch,
+        };
+        let h = |s: State| {
+            let d = s.pos.0.abs_diff(goal.pos.0) + s.pos.1.abs_diff(goal.pos.1);
+            d * MOVE + if s.tool == goal.tool { 0 } else { SWITCH }
+        };
+        let search = search::astar(self, &[start], Table::default(), |s| s == goal, h, |_| ());
+
+        // The target is always reachable: every region fits two tools.
+        Route {
+            time: search.dist(goal).unwrap(),
+            states: search.path(goal).unwrap(),
+        }
+    }
+}
+
+fn sum(grid: &mut Grid) -> u32 {
+    let mut sum = 0;
+    for x in 0..=grid.target.0 {
+        for y in 0..=grid.target.1 {
+            let x = match grid.cell(x, y) {
+                '.' => 0,
+                '=' => 1,
+                '|' => 2,
+                _ => unreachable!(),
+            };
+            sum += x;
+        }
+    }
+    sum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve() {
+        let scan = Day22::parse("depth: 510\ntarget: 10,10\n").unwrap();
+        assert_eq!(
+            scan,
+            Scan {
+                depth: 510,
+                target: (10, 10)
+            }
+        );
+        assert_eq!(Day22::part1(&scan), "114");
+        assert_eq!(Day22::part2(&scan), "45");
+        assert!(Day22::parse("depth: 510\ntarget: 10").is_err());
+        // Neither depth nor coordinates can be negative.
+        assert!(Day22::parse("depth: -510\ntarget: 10,10").is_err());
+        assert!(Day22::parse("depth: 510\ntarget: -10,10").is_err());
+        assert!(Day22::parse("depth: 510\ntarget: 10,-10").is_err());
+    }
+
+    #[test]
+    fn test_render() {
+        let mut grid = Grid::parse("depth: 510\ntarget: 10,10\n").unwrap();
+        assert_eq!(
+            grid.render((0, 0), (15, 15)),
+            vec![
+                "M=.|=.|.|=.|=|=.",
+                ".|=|=|||..|.=...",
+                ".==|....||=..|==",
+                "=.|....|.==.|==.",
+                "=|..==...=.|==..",
+                "=||.=.=||=|=..|=",
+                "|.=.===|||..=..|",
+                "|..==||=.|==|===",
+                ".=..===..=|.|||.",
+                ".======|||=|=.|=",
+                ".===|=|===T===||",
+                "=|||...|==..|=.|",
+                "=.=|=.=..=.||==|",
+                "||=|=...|==.=|==",
+                "|=.=||===.|||===",
+                "||.|==.|.|.||=||",
+            ]
+        );
+        // Any rectangle, generated on demand.
+        assert_eq!(grid.render((9, 9), (11, 11)), vec!["|=|", "=T=", "=.."]);
+        assert_eq!(grid.render((100, 1000), (101, 1000)).len(), 1);
+    }
+
+    #[test]
+    fn test_route() {
+        let mut grid = Grid::parse("depth: 510\ntarget: 10,10\n").unwrap();
+        let route = grid.route();
+        assert_eq!(route.time, 45);
+
+        // Every step is either a move to an adjacent region or a switch of tools,
+        // always with a tool fitting the region, and adds up to the total time.
+        let mut time = 0;
+        for w in route.states.windows(2) {
+            let (a, b) = (w[0], w[1]);
+            let d = a.pos.0.abs_diff(b.pos.0) + a.pos.1.abs_diff(b.pos.1);
+            if a.tool == b.tool {
+                assert_eq!(d, 1);
+                time += MOVE;
+            } else {
+                assert_eq!(d, 0);
+                time += SWITCH;
+            }
+            assert!(b.tool.fits(grid.cell(b.pos.0, b.pos.1)));
+        }
+        assert_eq!(time, route.time);
+        assert_eq!(route.states.first().map(|s| s.pos), Some((0, 0)));
+        assert_eq!(
+            route.states.last().map(|s| (s.pos, s.tool)),
+            Some(((10, 10), Tool::Torch))
+        );
+
+        // Every move is one minute, every switch seven.
+        let switches = route.switches().len();
+        let moves = route.states.len() - 1 - switches;
+        assert_eq!(moves * MOVE + switches * SWITCH, 45);
+        assert!(route.to_string().lines().count() == route.states.len() - 1);
+    }
+}
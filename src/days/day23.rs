@@ -0,0 +1,248 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::error::Error;
+
+use super::Solution;
+
+pub struct Day23;
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct Pos(i64, i64, i64);
+
+impl Pos {
+    fn distance_to(&self, that: &Pos) -> u64 {
+        ((that.0 - self.0).abs() + (that.1 - self.1).abs() + (that.2 - self.2).abs()) as u64
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct Rec {
+    pos: Pos,
+    r: u64,
+}
+
+impl Rec {
+    fn in_range(&self, that: &Pos) -> bool {
+        self.pos.distance_to(that) <= self.r
+    }
+}
+
+fn parse_input(lines: Vec<String>) -> Result<Vec<Rec>, String> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            parse_rec(line)
+                .ok_or_else(|| format!("line {}: expected a nanobot, found '{}'", i + 1, line))
+        })
+        .collect()
+}
+
+// pos=<77257099,50252980,47219056>, r=92361671
+fn parse_rec(line: &str) -> Option<Rec> {
+    let rest = line.trim().strip_prefix("pos=<")?;
+    let (pos, r) = rest.split_once(">, r=")?;
+    let mut it = pos.split(',').map(|n| n.trim().parse::<i64>());
+    let x = it.next()?.ok()?;
+    let y = it.next()?.ok()?;
+    let z = it.next()?.ok()?;
+    if it.next().is_some() {
+        return None;
+    }
+    Some(Rec {
+        pos: Pos(x, y, z),
+        r: r.trim().parse().ok()?,
+    })
+}
+
+fn find_strongest(recs: &[Rec]) -> usize {
+    let mut max: u64 = 0;
+    let mut idx: usize = 0;
+    for (i, rec) in recs.iter().enumerate() {
+        if rec.r > max {
+            max = rec.r;
+            idx = i;
+        }
+    }
+    idx
+}
+
+fn find_in_range(idx: usize, recs: &[Rec]) -> usize {
+    let mut count = 0;
+    let strongest = &recs[idx];
+    for rec in recs.iter() {
+        if strongest.in_range(&rec.pos) {
+            count += 1;
+        }
+    }
+    count
+}
+
+fn solve1(recs: &[Rec]) -> usize {
+    let idx = find_strongest(recs);
+    find_in_range(idx, recs)
+}
+
+// Axis-aligned cube of `size` (a power of two) points along each axis, starting at `min`.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+struct Cube {
+    min: Pos,
+    size: i64,
+}
+
+impl Cube {
+    // Smallest distance from `p` to any point of the cube.
+    fn distance_to(&self, p: &Pos) -> u64 {
+        let axis = |lo: i64, x: i64| {
+            let hi = lo + self.size - 1;
+            (lo - x).max(0) + (x - hi).max(0)
+        };
+        (axis(self.min.0, p.0) + axis(self.min.1, p.1) + axis(self.min.2, p.2)) as u64
+    }
+
+    fn split(&self) -> impl Iterator<Item = Cube> + '_ {
+        let half = self.size / 2;
+        (0..8).map(move |i| Cube {
+            min: Pos(
+                self.min.0 + half * (i & 1),
+                self.min.1 + half * ((i >> 1) & 1),
+                self.min.2 + half * ((i >> 2) & 1),
+            ),
+            size: half,
+        })
+    }
+
+    // Nanobots with the cube (partially) in range.
+    fn count(&self, recs: &[Rec]) -> usize {
+        recs.iter()
+            .filter(|rec| self.distance_to(&rec.pos) <= rec.r)
+            .count()
+    }
+}
+
+// Point in range of the most nanobots, the closest to the origin among ties,
+// along with the number of nanobots in range.
+//
+// Best-first octree subdivision: a cube's count is an upper bound of the count of
+// any point inside it, and its distance to the origin is a lower bound. So the first
+// single-point cube popped off the queue ordered by (count desc, distance asc) wins.
+fn solve2(recs: &[Rec]) -> Option<(Pos, usize)> {
+    let extent = recs
+        .iter()
+        .map(|rec| rec.pos.0.abs().max(rec.pos.1.abs()).max(rec.pos.2.abs()) + rec.r as i64)
+        .max()?;
+    let mut size = 1;
+    while size < 2 * extent + 1 {
+        size *= 2;
+    }
+    let root = Cube {
+        min: Pos(-size / 2, -size / 2, -size / 2),
+        size,
+    };
+    let origin = Pos(0, 0, 0);
+
+    // Ties on the same distance are broken by size and then position, for determinism.
+    let entry = |cube: Cube| {
+        let count = cube.count(recs);
+        let distance = cube.distance_to(&origin);
+        let Pos(x, y, z) = cube.min;
+        (
+            count,
+            Reverse(distance),
+            Reverse(cube.size),
+            Reverse((x, y, z)),
+        )
+    };
+
+    let mut queue = BinaryHeap::new();
+    queue.push(entry(root));
+    while let Some((count, _, Reverse(size), Reverse((x, y, z)))) = queue.pop() {
+        let cube = Cube {
+            min: Pos(x, y, z),
+            size,
+        };
+        if size == 1 {
+            return Some((cube.min, count));
+        }
+        queue.extend(cube.split().map(entry));
+    }
+    None
+}
+
+impl Solution for Day23 {
+    type Input = Vec<Rec>;
+
+    fn parse(input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        let recs = parse_input(crate::input::lines(input))?;
+        if recs.is_empty() {
+            return Err("no nanobots".into());
+        }
+        Ok(recs)
+    }
+
+    // Nanobots in range of the strongest one.
+    fn part1(recs: &Self::Input) -> String {
+        solve1(recs).to_string()
+    }
+
+    // Distance to the origin of the best covered point.
+    fn part2(recs: &Self::Input) -> String {
+        match solve2(recs) {
+            Some((pos, _)) => pos.distance_to(&Pos(0, 0, 0)).to_string(),
+            None => "none".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_input() {
+        let input = vec![
+            String::from("pos=<77257099,50252980,47219056>, r=92361671"),
+            String::from("pos=<53167623,26016086,28898789>, r=74188754"),
+            String::from("pos=<8924567,89634339,-10531384>, r=73102870"),
+        ];
+
+        assert_eq!(
+            parse_input(input),
+            Ok(vec![
+                Rec {
+                    pos: Pos(77257099, 50252980, 47219056),
+                    r: 92361671
+                },
+                Rec {
+                    pos: Pos(53167623, 26016086, 28898789),
+                    r: 74188754
+                },
+                Rec {
+                    pos: Pos(8924567, 89634339, -10531384),
+                    r: 73102870
+                },
+            ])
+        );
+        assert!(parse_input(vec![String::from("pos=<1,2>, r=3")]).is_err());
+    }
+
+    #[test]
+    fn test_solve2() {
+        let recs = parse_input(
+            vec![
+                "pos=<10,12,12>, r=2",
+                "pos=<12,14,12>, r=2",
+                "pos=<16,12,12>, r=4",
+                "pos=<14,14,14>, r=6",
+                "pos=<50,50,50>, r=200",
+                "pos=<10,10,10>, r=5",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+        )
+        .unwrap();
+        assert_eq!(solve2(&recs), Some((Pos(12, 12, 12), 5)));
+        assert_eq!(Day23::part2(&recs), "36");
+    }
+}
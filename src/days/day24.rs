@@ -0,0 +1,361 @@
+use std::cmp::Reverse;
+use std::error::Error;
+use std::fmt;
+
+use super::Solution;
+
+pub struct Day24;
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Army {
+    Immune,
+    Infection,
+}
+
+impl fmt::Display for Army {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Army::Immune => write!(f, "Immune System"),
+            Army::Infection => write!(f, "Infection"),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Group {
+    army: Army,
+    units: usize,
+    hp: usize,
+    immune: Vec<String>,
+    weak: Vec<String>,
+    damage: usize,
+    kind: String,
+    initiative: usize,
+}
+
+impl Group {
+    fn power(&self) -> usize {
+        self.units * self.damage
+    }
+
+    // Damage this group would deal to `that`, with immunities and weaknesses.
+    fn damage_to(&self, that: &Group) -> usize {
+        if that.immune.contains(&self.kind) {
+            0
+        } else if that.weak.contains(&self.kind) {
+            2 * self.power()
+        } else {
+            self.power()
+        }
+    }
+}
+
+// (immune to cold; weak to bludgeoning), in either order and each optional.
+fn parse_traits(text: &str, group: &mut Group) -> Option<()> {
+    for clause in text.split(';') {
+        let clause = clause.trim();
+        let (list, kinds) = if let Some(kinds) = clause.strip_prefix("immune to ") {
+            (&mut group.immune, kinds)
+        } else if let Some(kinds) = clause.strip_prefix("weak to ") {
+            (&mut group.weak, kinds)
+        } else {
+            return None;
+        };
+        if !list.is_empty() {
+            return None;
+        }
+        list.extend(kinds.split(',').map(|k| k.trim().to_string()));
+    }
+    Some(())
+}
+
+// 18 units each with 729 hit points (weak to fire; immune to cold, slashing)
+//  with an attack that does 8 radiation damage at initiative 10
+fn parse_group(line: &str, army: Army) -> Option<Group> {
+    let (units, rest) = line.trim().split_once(" units each with ")?;
+    let (hp, rest) = rest.split_once(" hit points ")?;
+    let mut group = Group {
+        army,
+        units: units.parse().ok()?,
+        hp: hp.parse().ok()?,
+        immune: vec![],
+        weak: vec![],
+        damage: 0,
+        kind: String::new(),
+        initiative: 0,
+    };
+    let rest = match rest.strip_prefix('(') {
+        Some(traits) => {
+            let (traits, rest) = traits.split_once(") ")?;
+            parse_traits(traits, &mut group)?;
+            rest
+        }
+        None => rest,
+    };
+    let rest = rest.strip_prefix("with an attack that does ")?;
+    let (attack, initiative) = rest.split_once(" damage at initiative ")?;
+    let (damage, kind) = attack.split_once(' ')?;
+    group.damage = damage.parse().ok()?;
+    group.kind = kind.to_string();
+    group.initiative = initiative.trim().parse().ok()?;
+    Some(group)
+}
+
+fn parse_groups(input: &str) -> Result<Vec<Group>, String> {
+    let mut groups = Vec::new();
+    let mut army = None;
+    for (i, line) in crate::input::lines(input).iter().enumerate() {
+        match line.trim() {
+            "" => (),
+            "Immune System:" => army = Some(Army::Immune),
+            "Infection:" => army = Some(Army::Infection),
+            _ => {
+                let army = army.ok_or_else(|| {
+                    format!("line {}: expected an army header, found '{}'", i + 1, line)
+                })?;
+                let group = parse_group(line, army)
+                    .ok_or_else(|| format!("line {}: invalid group '{}'", i + 1, line))?;
+                groups.push(group);
+            }
+        }
+    }
+    Ok(groups)
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+enum Outcome {
+    // Winning army and its units left.
+    Win(Army, usize),
+    // Nobody can kill a single unit anymore.
+    Stalemate,
+}
+
+// Returns the number of units killed over the whole round.
+fn round(groups: &mut Vec<Group>) -> usize {
+    // Target selection: by effective power, then by initiative.
+    let mut order: Vec<usize> = (0..groups.len()).collect();
+    order.sort_by_key(|&i| Reverse((groups[i].power(), groups[i].initiative)));
+    let mut targets: Vec<Option<usize>> = vec![None; groups.len()];
+    let mut taken = vec![false; groups.len()];
+    for &i in &order {
+        let attacker = &groups[i];
+        let target = (0..groups.len())
+            .filter(|&j| !taken[j] && groups[j].army != attacker.army)
+            .filter(|&j| attacker.damage_to(&groups[j]) > 0)
+            .max_by_key(|&j| {
+                let that = &groups[j];
+                (attacker.damage_to(that), that.power(), that.initiative)
+            });
+        if let Some(j) = target {
+            taken[j] = true;
+            targets[i] = Some(j);
+        }
+    }
+
+    // Attacking: by initiative only, dead groups don't attack.
+    order.sort_by_key(|&i| Reverse(groups[i].initiative));
+    let mut killed = 0;
+    for i in order {
+        if let Some(j) = targets[i] {
+            if groups[i].units == 0 {
+                continue;
+            }
+            let damage = groups[i].damage_to(&groups[j]);
+            let target = &mut groups[j];
+            let dead = (damage / target.hp).min(target.units);
+            target.units -= dead;
+            killed += dead;
+        }
+    }
+    groups.retain(|g| g.units > 0);
+    killed
+}
+
+fn fight(groups: &[Group], boost: usize) -> Outcome {
+    let mut groups: Vec<Group> = groups
+        .iter()
+        .map(|g| match g.army {
+            Army::Immune => Group {
+                damage: g.damage + boost,
+                ..g.clone()
+            },
+            Army::Infection => g.clone(),
+        })
+        .collect();
+    loop {
+        let alive = |army| groups.iter().any(|g| g.army == army);
+        match (alive(Army::Immune), alive(Army::Infection)) {
+            (true, true) => (),
+            (immune, _) => {
+                let army = if immune {
+                    Army::Immune
+                } else {
+                    Army::Infection
+                };
+                return Outcome::Win(army, groups.iter().map(|g| g.units).sum());
+            }
+        }
+        if round(&mut groups) == 0 {
+            return Outcome::Stalemate;
+        }
+    }
+}
+
+// Smallest boost that lets the immune system win, and units it is left with.
+// Winning isn't monotonic in the boost (stalemates), so boosts doubling from 1 are
+// only probed for a win, then everything below the first winning one is scanned.
+// Past the boost that kills every infection group with a single hit nothing changes,
+// and no boost helps against a group immune to every attack of the immune system.
+fn rescue(groups: &[Group]) -> Option<(usize, usize)> {
+    let infection = groups.iter().filter(|g| g.army == Army::Infection);
+    let immune: Vec<&Group> = groups.iter().filter(|g| g.army == Army::Immune).collect();
+    let invulnerable = |g: &Group| immune.iter().all(|i| g.immune.contains(&i.kind));
+    if infection.clone().any(invulnerable) {
+        return None;
+    }
+    let cap = infection.map(|g| g.units * g.hp).max().unwrap_or(0);
+
+    let wins = |boost| match fight(groups, boost) {
+        Outcome::Win(Army::Immune, units) => Some((boost, units)),
+        _ => None,
+    };
+    let mut probe = 1;
+    let found = loop {
+        let boost = probe.min(cap);
+        if let Some(found) = wins(boost) {
+            break found;
+        }
+        if boost == cap {
+            return None;
+        }
+        probe *= 2;
+    };
+    (0..found.0).find_map(wins).or(Some(found))
+}
+
+impl Solution for Day24 {
+    type Input = Vec<Group>;
+
+    fn parse(input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        let groups = parse_groups(input)?;
+        for army in [Army::Immune, Army::Infection].iter() {
+            if !groups.iter().any(|g| g.army == *army) {
+                return Err(format!("no groups in the {} army", army).into());
+            }
+        }
+        Ok(groups)
+    }
+
+    // Units left in the winning army.
+    fn part1(input: &Self::Input) -> String {
+        match fight(input, 0) {
+            Outcome::Win(_, units) => units.to_string(),
+            Outcome::Stalemate => "stalemate".to_string(),
+        }
+    }
+
+    // Units left in the immune system with the smallest boost to win.
+    fn part2(input: &Self::Input) -> String {
+        match rescue(input) {
+            Some((_, units)) => units.to_string(),
+            None => "none".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Immune System:
+17 units each with 5390 hit points (weak to radiation, bludgeoning) with an attack that does 4507 fire damage at initiative 2
+989 units each with 1274 hit points (immune to fire; weak to bludgeoning, slashing) with an attack that does 25 slashing damage at initiative 3
+
+Infection:
+801 units each with 4706 hit points (weak to radiation) with an attack that does 116 bludgeoning damage at initiative 1
+4485 units each with 2961 hit points (immune to radiation; weak to fire, cold) with an attack that does 12 slashing damage at initiative 4
+";
+
+    #[test]
+    fn test_parse() {
+        let groups = Day24::parse(EXAMPLE).unwrap();
+        assert_eq!(groups.len(), 4);
+        assert_eq!(
+            groups[1],
+            Group {
+                army: Army::Immune,
+                units: 989,
+                hp: 1274,
+                immune: vec!["fire".to_string()],
+                weak: vec!["bludgeoning".to_string(), "slashing".to_string()],
+                damage: 25,
+                kind: "slashing".to_string(),
+                initiative: 3,
+            }
+        );
+
+        // Traits in either order, or none at all.
+        let line = "10 units each with 20 hit points (weak to fire; immune to cold, slashing) \
+                    with an attack that does 8 radiation damage at initiative 10";
+        let group = parse_group(line, Army::Infection).unwrap();
+        assert_eq!(group.weak, vec!["fire"]);
+        assert_eq!(group.immune, vec!["cold", "slashing"]);
+        let line = "10 units each with 20 hit points with an attack that does 8 fire damage at initiative 1";
+        assert!(parse_group(line, Army::Infection).is_some());
+
+        assert!(Day24::parse("Immune System:\n10 units each with 20 hit points").is_err());
+        assert!(Day24::parse(&EXAMPLE[..EXAMPLE.find("Infection").unwrap()]).is_err());
+    }
+
+    #[test]
+    fn test_fight() {
+        let groups = Day24::parse(EXAMPLE).unwrap();
+        assert_eq!(fight(&groups, 0), Outcome::Win(Army::Infection, 5216));
+        assert_eq!(fight(&groups, 1570), Outcome::Win(Army::Immune, 51));
+        assert_eq!(rescue(&groups), Some((1570, 51)));
+    }
+
+    #[test]
+    fn test_stalemate() {
+        // Both groups are immune to the other one's attacks.
+        let text = "Immune System:
+10 units each with 10 hit points (immune to fire) with an attack that does 5 cold damage at initiative 1
+
+Infection:
+10 units each with 10 hit points (immune to cold) with an attack that does 5 fire damage at initiative 2
+";
+        let groups = Day24::parse(text).unwrap();
+        assert_eq!(fight(&groups, 0), Outcome::Stalemate);
+        assert_eq!(rescue(&groups), None);
+    }
+
+    #[test]
+    fn test_unwinnable() {
+        // No boost can hurt the second infection group, however many hit points it has.
+        let text = "Immune System:
+10 units each with 10 hit points with an attack that does 5 cold damage at initiative 1
+10 units each with 10 hit points with an attack that does 5 fire damage at initiative 2
+
+Infection:
+10 units each with 10 hit points with an attack that does 5 fire damage at initiative 3
+100000 units each with 100000 hit points (immune to cold, fire) with an attack that does 1 fire damage at initiative 4
+";
+        let groups = Day24::parse(text).unwrap();
+        assert_eq!(rescue(&groups), None);
+    }
+
+    #[test]
+    fn test_large_boost() {
+        // Only a hit killing the infection at once wins: doubling the boost gets past it
+        // quickly, the smallest winning boost is found below.
+        let text = "Immune System:
+1 units each with 10 hit points with an attack that does 1 cold damage at initiative 2
+
+Infection:
+1 units each with 1000 hit points with an attack that does 20 fire damage at initiative 1
+";
+        let groups = Day24::parse(text).unwrap();
+        assert_eq!(fight(&groups, 0), Outcome::Win(Army::Infection, 1));
+        assert_eq!(rescue(&groups), Some((999, 1)));
+    }
+}
@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+use std::error::Error;
+
+use super::Solution;
+use crate::disjoint::DisjointSet;
+
+pub struct Day25;
+
+type Point = [i64; 4];
+
+// Points this close (or closer) belong to the same constellation.
+const REACH: i64 = 3;
+
+fn distance(a: &Point, b: &Point) -> i64 {
+    a.iter().zip(b.iter()).map(|(x, y)| (x - y).abs()).sum()
+}
+
+// Cube of side REACH + 1 containing the point: points within reach can only
+// be in the same or an adjacent bucket along every axis.
+fn bucket(p: &Point) -> Point {
+    let mut key = [0; 4];
+    for (k, x) in key.iter_mut().zip(p.iter()) {
+        *k = x.div_euclid(REACH + 1);
+    }
+    key
+}
+
+// All 3^4 buckets around (and including) the given one.
+fn around(key: Point) -> impl Iterator<Item = Point> {
+    (0..81).map(move |mut n| {
+        let mut k = key;
+        for x in k.iter_mut() {
+            *x += n % 3 - 1;
+            n /= 3;
+        }
+        k
+    })
+}
+
+fn constellations(points: &[Point]) -> usize {
+    let mut buckets: HashMap<Point, Vec<usize>> = HashMap::new();
+    for (i, p) in points.iter().enumerate() {
+        buckets.entry(bucket(p)).or_default().push(i);
+    }
+
+    let mut sets = DisjointSet::new(points.len());
+    for (i, p) in points.iter().enumerate() {
+        for key in around(bucket(p)) {
+            for &j in buckets.get(&key).into_iter().flatten() {
+                if j > i && distance(p, &points[j]) <= REACH {
+                    sets.union(i, j);
+                }
+            }
+        }
+    }
+    sets.sets()
+}
+
+fn parse_point(line: &str) -> Option<Point> {
+    let mut point = [0; 4];
+    let mut it = line.split(',');
+    for x in point.iter_mut() {
+        *x = it.next()?.trim().parse().ok()?;
+    }
+    match it.next() {
+        Some(_) => None,
+        None => Some(point),
+    }
+}
+
+impl Solution for Day25 {
+    type Input = Vec<Point>;
+
+    fn parse(input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        crate::input::lines(input)
+            .iter()
+            .enumerate()
+            .map(|(i, line)| {
+                parse_point(line)
+                    .ok_or_else(|| format!("line {}: invalid point '{}'", i + 1, line).into())
+            })
+            .collect()
+    }
+
+    fn part1(input: &Self::Input) -> String {
+        constellations(input).to_string()
+    }
+
+    // There is no second puzzle on the last day.
+    fn part2(_: &Self::Input) -> String {
+        "n/a".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count(text: &str) -> usize {
+        constellations(&Day25::parse(text).unwrap())
+    }
+
+    #[test]
+    fn test_constellations() {
+        let text = "0,0,0,0\n3,0,0,0\n0,3,0,0\n0,0,3,0\n0,0,0,3\n0,0,0,6\n9,0,0,0\n12,0,0,0\n";
+        assert_eq!(count(text), 2);
+        let text = "-1,2,2,0\n0,0,2,-2\n0,0,0,-2\n-1,2,0,0\n-2,-2,-2,2\n3,0,2,-1\n\
+                    -1,3,2,2\n-1,0,-1,0\n0,2,1,-2\n3,0,0,0\n";
+        assert_eq!(count(text), 4);
+        let text = "1,-1,0,1\n2,0,-1,0\n3,2,-1,0\n0,0,3,1\n0,0,-1,-1\n2,3,-2,0\n\
+                    -2,2,0,0\n2,-2,0,-1\n1,-1,0,-1\n3,2,0,2\n";
+        assert_eq!(count(text), 3);
+        let text = "1,-1,-1,-2\n-2,-2,0,1\n0,2,1,3\n-2,3,-2,1\n0,2,3,-2\n-1,-1,1,-2\n\
+                    0,-2,-1,0\n-2,2,3,-1\n1,2,2,0\n-1,-2,0,-2\n";
+        assert_eq!(count(text), 8);
+        assert!(Day25::parse("1,2,3").is_err());
+    }
+
+    #[test]
+    fn test_buckets() {
+        // Points in reach (even across a bucket border) are in neighbouring buckets,
+        // far apart points aren't.
+        let (a, b) = ([0, 0, 0, 0], [-3, 0, 0, 0]);
+        assert_ne!(bucket(&a), bucket(&b));
+        assert!(around(bucket(&a)).any(|k| k == bucket(&b)));
+        assert!(!around(bucket(&a)).any(|k| k == bucket(&[8, 0, 0, 0])));
+        assert_eq!(around(bucket(&a)).count(), 81);
+    }
+}
@@ -0,0 +1,261 @@
+use std::fmt;
+
+pub mod cfg;
+mod compile;
+pub mod debug;
+mod disasm;
+pub mod flow;
+pub mod infer;
+mod parse;
+pub mod samples;
+mod transpile;
+
+pub use self::compile::{Compiled, Insn};
+pub use self::disasm::{disassemble, pseudo};
+pub use self::parse::{parse, ErrorKind, ParseError};
+pub use self::transpile::transpile;
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+pub enum Opcode {
+    Addr,
+    Addi,
+    Mulr,
+    Muli,
+    Banr,
+    Bani,
+    Borr,
+    Bori,
+    Setr,
+    Seti,
+    Gtir,
+    Gtri,
+    Gtrr,
+    Eqir,
+    Eqri,
+    Eqrr,
+}
+
+impl Opcode {
+    pub const ALL: [Opcode; 16] = [
+        Opcode::Addr,
+        Opcode::Addi,
+        Opcode::Mulr,
+        Opcode::Muli,
+        Opcode::Banr,
+        Opcode::Bani,
+        Opcode::Borr,
+        Opcode::Bori,
+        Opcode::Setr,
+        Opcode::Seti,
+        Opcode::Gtir,
+        Opcode::Gtri,
+        Opcode::Gtrr,
+        Opcode::Eqir,
+        Opcode::Eqri,
+        Opcode::Eqrr,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Opcode::Addr => "addr",
+            Opcode::Addi => "addi",
+            Opcode::Mulr => "mulr",
+            Opcode::Muli => "muli",
+            Opcode::Banr => "banr",
+            Opcode::Bani => "bani",
+            Opcode::Borr => "borr",
+            Opcode::Bori => "bori",
+            Opcode::Setr => "setr",
+            Opcode::Seti => "seti",
+            Opcode::Gtir => "gtir",
+            Opcode::Gtri => "gtri",
+            Opcode::Gtrr => "gtrr",
+            Opcode::Eqir => "eqir",
+            Opcode::Eqri => "eqri",
+            Opcode::Eqrr => "eqrr",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Opcode> {
+        Opcode::ALL.iter().copied().find(|op| op.name() == name)
+    }
+
+    // Tells which of the operands (a, b) are register indices.
+    pub fn reads(self) -> (bool, bool) {
+        match self {
+            Opcode::Addr
+            | Opcode::Mulr
+            | Opcode::Banr
+            | Opcode::Borr
+            | Opcode::Gtrr
+            | Opcode::Eqrr => (true, true),
+            Opcode::Addi
+            | Opcode::Muli
+            | Opcode::Bani
+            | Opcode::Bori
+            | Opcode::Setr
+            | Opcode::Gtri
+            | Opcode::Eqri => (true, false),
+            Opcode::Gtir | Opcode::Eqir => (false, true),
+            Opcode::Seti => (false, false),
+        }
+    }
+
+    #[inline]
+    pub fn eval(self, a: usize, b: usize, regs: &[usize]) -> usize {
+        match self {
+            Opcode::Addr => regs[a] + regs[b],
+            Opcode::Addi => regs[a] + b,
+            Opcode::Mulr => regs[a] * regs[b],
+            Opcode::Muli => regs[a] * b,
+            Opcode::Banr => regs[a] & regs[b],
+            Opcode::Bani => regs[a] & b,
+            Opcode::Borr => regs[a] | regs[b],
+            Opcode::Bori => regs[a] | b,
+            Opcode::Setr => regs[a],
+            Opcode::Seti => a,
+            Opcode::Gtir => usize::from(a > regs[b]),
+            Opcode::Gtri => usize::from(regs[a] > b),
+            Opcode::Gtrr => usize::from(regs[a] > regs[b]),
+            Opcode::Eqir => usize::from(a == regs[b]),
+            Opcode::Eqri => usize::from(regs[a] == b),
+            Opcode::Eqrr => usize::from(regs[a] == regs[b]),
+        }
+    }
+}
+
+impl fmt::Display for Opcode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+pub struct Code {
+    pub op: Opcode,
+    pub a: usize,
+    pub b: usize,
+    pub c: usize,
+}
+
+impl Code {
+    pub fn new(op: Opcode, a: usize, b: usize, c: usize) -> Code {
+        Code { op, a, b, c }
+    }
+
+    #[inline]
+    pub fn apply(&self, regs: &mut [usize]) {
+        regs[self.c] = self.op.eval(self.a, self.b, regs);
+    }
+}
+
+impl fmt::Display for Code {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} {} {}", self.op, self.a, self.b, self.c)
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Program {
+    pub ip: Option<usize>,
+    pub codes: Vec<Code>,
+}
+
+// Register file of N registers with an optional instruction pointer binding.
+// Bound register receives the IP before each instruction, and the IP is read
+// back from it afterwards, then incremented (the register itself is not).
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+pub struct Machine<const N: usize> {
+    pub ip: usize,
+    pub bound: Option<usize>,
+    pub regs: [usize; N],
+}
+
+impl<const N: usize> Machine<N> {
+    pub fn new(bound: Option<usize>, regs: [usize; N]) -> Machine<N> {
+        Machine { ip: 0, bound, regs }
+    }
+
+    pub fn get(&self, idx: usize) -> usize {
+        self.regs[idx]
+    }
+
+    pub fn set(&mut self, idx: usize, val: usize) {
+        self.regs[idx] = val;
+    }
+
+    pub fn halted(&self, codes: &[Code]) -> bool {
+        self.ip >= codes.len()
+    }
+
+    // Executes a single instruction bypassing the instruction pointer.
+    pub fn exec(&mut self, code: &Code) {
+        code.apply(&mut self.regs);
+    }
+
+    // Executes the instruction at IP, returns false if the machine is halted.
+    pub fn step(&mut self, codes: &[Code]) -> bool {
+        let code = match codes.get(self.ip) {
+            Some(code) => code,
+            None => return false,
+        };
+        if let Some(r) = self.bound {
+            self.regs[r] = self.ip;
+        }
+        code.apply(&mut self.regs);
+        if let Some(r) = self.bound {
+            self.ip = self.regs[r];
+        }
+        self.ip += 1;
+        true
+    }
+
+    pub fn run(&mut self, codes: &[Code]) {
+        while self.step(codes) {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_opcode_names() {
+        for op in Opcode::ALL.iter() {
+            assert_eq!(Opcode::from_name(op.name()), Some(*op));
+        }
+        assert_eq!(Opcode::from_name("nope"), None);
+    }
+
+    #[test]
+    fn test_exec() {
+        let mut m = Machine::new(None, [3, 2, 1, 1]);
+        m.exec(&Code::new(Opcode::Mulr, 2, 1, 2));
+        assert_eq!(m.regs, [3, 2, 2, 1]);
+        m.exec(&Code::new(Opcode::Addi, 2, 1, 2));
+        assert_eq!(m.regs, [3, 2, 3, 1]);
+        m.exec(&Code::new(Opcode::Seti, 2, 1, 2));
+        assert_eq!(m.regs, [3, 2, 2, 1]);
+        m.exec(&Code::new(Opcode::Gtri, 0, 2, 3));
+        assert_eq!(m.regs, [3, 2, 2, 1]);
+        m.exec(&Code::new(Opcode::Gtir, 0, 2, 3));
+        assert_eq!(m.regs, [3, 2, 2, 0]);
+    }
+
+    #[test]
+    fn test_run() {
+        let codes = vec![
+            Code::new(Opcode::Seti, 5, 0, 1),
+            Code::new(Opcode::Seti, 6, 0, 2),
+            Code::new(Opcode::Addi, 0, 1, 0),
+            Code::new(Opcode::Addr, 1, 2, 3),
+            Code::new(Opcode::Setr, 1, 0, 0),
+            Code::new(Opcode::Seti, 8, 0, 4),
+            Code::new(Opcode::Seti, 9, 0, 5),
+        ];
+        let mut m = Machine::new(Some(0), [0; 6]);
+        m.run(&codes);
+        assert_eq!(m.ip, 7);
+        assert_eq!(m.regs, [6, 5, 6, 0, 0, 9]);
+    }
+}
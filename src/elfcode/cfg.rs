@@ -0,0 +1,305 @@
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+use super::flow::{flow, leaders, Flow};
+use super::{Opcode, Program};
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Block {
+    // Instruction index range [start, end).
+    pub start: usize,
+    pub end: usize,
+    // Indices of successor blocks.
+    pub succ: Vec<usize>,
+    // True if control can leave the program from this block.
+    pub exit: bool,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Loop {
+    pub header: usize,
+    pub body: BTreeSet<usize>,
+    // Registers only ever changed within the loop by adding a constant to them.
+    pub induction: Vec<usize>,
+}
+
+impl Loop {
+    pub fn contains(&self, block: usize) -> bool {
+        self.body.contains(&block)
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Cfg {
+    pub blocks: Vec<Block>,
+}
+
+impl Cfg {
+    // Splits the program into basic blocks and connects them according to
+    // the IP writes at block ends. Jumps by a register offset may land on
+    // any later block and indirect jumps anywhere, so both are treated
+    // conservatively.
+    pub fn build(program: &Program) -> Cfg {
+        let n = program.codes.len();
+        let starts: Vec<usize> = leaders(program).into_iter().collect();
+        let block_of = |idx: usize| starts.binary_search(&idx).ok();
+
+        let mut blocks = Vec::with_capacity(starts.len());
+        for (i, &start) in starts.iter().enumerate() {
+            let end = starts.get(i + 1).cloned().unwrap_or(n);
+            let last = end - 1;
+            let targets: Vec<usize> = match flow(program, last) {
+                Flow::Next => vec![end],
+                Flow::Jump(to) => vec![to],
+                Flow::Branch { .. } => vec![last + 1, last + 2],
+                Flow::Offset { .. } => starts
+                    .iter()
+                    .cloned()
+                    .filter(|s| *s > last)
+                    .chain(Some(n))
+                    .collect(),
+                Flow::Indirect => starts.iter().cloned().chain(Some(n)).collect(),
+            };
+            let mut succ: Vec<usize> = targets.iter().filter_map(|t| block_of(*t)).collect();
+            succ.sort_unstable();
+            succ.dedup();
+            let exit = targets.iter().any(|t| *t >= n);
+            blocks.push(Block {
+                start,
+                end,
+                succ,
+                exit,
+            });
+        }
+        Cfg { blocks }
+    }
+
+    pub fn block_of(&self, idx: usize) -> Option<usize> {
+        self.blocks
+            .iter()
+            .position(|b| b.start <= idx && idx < b.end)
+    }
+
+    pub fn preds(&self, block: usize) -> Vec<usize> {
+        (0..self.blocks.len())
+            .filter(|b| self.blocks[*b].succ.contains(&block))
+            .collect()
+    }
+
+    // Dominator sets of every block, computed iteratively from the entry.
+    // Unreachable blocks end up dominated by every block.
+    pub fn dominators(&self) -> Vec<BTreeSet<usize>> {
+        let n = self.blocks.len();
+        let all: BTreeSet<usize> = (0..n).collect();
+        let mut dom = vec![all; n];
+        if n == 0 {
+            return dom;
+        }
+        dom[0] = Some(0).into_iter().collect();
+
+        let preds: Vec<Vec<usize>> = (0..n).map(|b| self.preds(b)).collect();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for b in 1..n {
+                let mut next: Option<BTreeSet<usize>> = None;
+                for p in &preds[b] {
+                    next = Some(match next {
+                        None => dom[*p].clone(),
+                        Some(acc) => acc.intersection(&dom[*p]).cloned().collect(),
+                    });
+                }
+                let mut next = next.unwrap_or_default();
+                next.insert(b);
+                if next != dom[b] {
+                    dom[b] = next;
+                    changed = true;
+                }
+            }
+        }
+        dom
+    }
+
+    // Natural loops: for every back edge (an edge to a dominating block) the
+    // loop consists of the header and every block reaching the edge source
+    // without passing through the header. Loops sharing a header are merged.
+    pub fn loops(&self, program: &Program) -> Vec<Loop> {
+        let dom = self.dominators();
+        let mut loops: Vec<Loop> = Vec::new();
+        for (src, block) in self.blocks.iter().enumerate() {
+            for &header in &block.succ {
+                if !dom[src].contains(&header) {
+                    continue;
+                }
+                let mut body: BTreeSet<usize> = vec![header].into_iter().collect();
+                let mut stack = vec![src];
+                while let Some(b) = stack.pop() {
+                    if body.insert(b) {
+                        stack.extend(self.preds(b));
+                    }
+                }
+                match loops.iter_mut().find(|l| l.header == header) {
+                    Some(l) => l.body.extend(body),
+                    None => loops.push(Loop {
+                        header,
+                        body,
+                        induction: Vec::new(),
+                    }),
+                }
+            }
+        }
+
+        for l in loops.iter_mut() {
+            l.induction = self.induction(program, &l.body);
+        }
+        loops.sort_by_key(|l| (l.body.len(), l.header));
+        loops
+    }
+
+    fn induction(&self, program: &Program, body: &BTreeSet<usize>) -> Vec<usize> {
+        let mut writes: Vec<Vec<(Opcode, usize, usize)>> = Vec::new();
+        for b in body {
+            let block = &self.blocks[*b];
+            for code in &program.codes[block.start..block.end] {
+                if writes.len() <= code.c {
+                    writes.resize(code.c + 1, Vec::new());
+                }
+                writes[code.c].push((code.op, code.a, code.b));
+            }
+        }
+        writes
+            .iter()
+            .enumerate()
+            .filter(|(r, _)| Some(*r) != program.ip)
+            .filter(|(r, ws)| {
+                ws.len() == 1 && ws.iter().all(|(op, a, _)| *op == Opcode::Addi && a == r)
+            })
+            .map(|(r, _)| r)
+            .collect()
+    }
+
+    // Loops that do not contain any other loop's header.
+    pub fn innermost<'a>(&self, loops: &'a [Loop]) -> Vec<&'a Loop> {
+        loops
+            .iter()
+            .filter(|l| {
+                !loops
+                    .iter()
+                    .any(|o| o.header != l.header && l.contains(o.header))
+            })
+            .collect()
+    }
+
+    // Renders the graph in Graphviz DOT format, back edges are dashed.
+    pub fn to_dot(&self, program: &Program) -> String {
+        let dom = self.dominators();
+        let mut out = String::new();
+        writeln!(out, "digraph elfcode {{").unwrap();
+        writeln!(out, "    node [shape=box, fontname=\"monospace\"];").unwrap();
+        for (i, block) in self.blocks.iter().enumerate() {
+            let mut label = format!("L{:02}\\l", block.start);
+            for idx in block.start..block.end {
+                write!(label, "{:02}: {}\\l", idx, program.codes[idx]).unwrap();
+            }
+            writeln!(out, "    b{} [label=\"{}\"];", i, label).unwrap();
+        }
+        if self.blocks.iter().any(|b| b.exit) {
+            writeln!(out, "    exit [shape=doublecircle];").unwrap();
+        }
+        for (i, block) in self.blocks.iter().enumerate() {
+            for s in &block.succ {
+                if dom[i].contains(s) {
+                    writeln!(out, "    b{} -> b{} [style=dashed];", i, s).unwrap();
+                } else {
+                    writeln!(out, "    b{} -> b{};", i, s).unwrap();
+                }
+            }
+            if block.exit {
+                writeln!(out, "    b{} -> exit;", i).unwrap();
+            }
+        }
+        writeln!(out, "}}").unwrap();
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elfcode::parse;
+
+    // Sums r[1] over 1..=r[2], with an inner loop counting r[5] up to r[2].
+    const NESTED: &str = "#ip 4
+seti 1 0 1
+seti 1 0 5
+addi 5 1 5
+gtrr 5 2 3
+addr 3 4 4
+seti 1 0 4
+addr 1 0 0
+addi 1 1 1
+gtrr 1 2 3
+addr 3 4 4
+seti 0 0 4";
+
+    #[test]
+    fn test_blocks() {
+        let program = parse(NESTED, 6).unwrap();
+        let cfg = Cfg::build(&program);
+        let spans: Vec<(usize, usize, Vec<usize>, bool)> = cfg
+            .blocks
+            .iter()
+            .map(|b| (b.start, b.end, b.succ.clone(), b.exit))
+            .collect();
+        assert_eq!(
+            spans,
+            vec![
+                (0, 1, vec![1], false),
+                (1, 2, vec![2], false),
+                (2, 5, vec![3, 4], false),
+                (5, 6, vec![2], false),
+                (6, 10, vec![5], true),
+                (10, 11, vec![1], false),
+            ]
+        );
+        assert_eq!(cfg.block_of(7), Some(4));
+    }
+
+    #[test]
+    fn test_loops() {
+        let program = parse(NESTED, 6).unwrap();
+        let cfg = Cfg::build(&program);
+        let loops = cfg.loops(&program);
+        let found: Vec<(usize, Vec<usize>, Vec<usize>)> = loops
+            .iter()
+            .map(|l| {
+                (
+                    l.header,
+                    l.body.iter().cloned().collect(),
+                    l.induction.clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![(2, vec![2, 3], vec![5]), (1, vec![1, 2, 3, 4, 5], vec![1])]
+        );
+        let inner: Vec<usize> = cfg.innermost(&loops).iter().map(|l| l.header).collect();
+        assert_eq!(inner, vec![2]);
+    }
+
+    #[test]
+    fn test_dot() {
+        let program = parse("#ip 1\nseti 0 0 0\naddi 0 1 0\nseti 0 0 1", 2).unwrap();
+        let cfg = Cfg::build(&program);
+        let expected = "digraph elfcode {
+    node [shape=box, fontname=\"monospace\"];
+    b0 [label=\"L00\\l00: seti 0 0 0\\l\"];
+    b1 [label=\"L01\\l01: addi 0 1 0\\l02: seti 0 0 1\\l\"];
+    b0 -> b1;
+    b1 -> b1 [style=dashed];
+}
+";
+        assert_eq!(cfg.to_dot(&program), expected);
+    }
+}
@@ -0,0 +1,481 @@
+use super::{Code, Machine, Opcode, Program};
+
+// Pre-decoded instruction. Operands reading the IP register are replaced by
+// the (statically known) instruction index, so only writes to it remain.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Insn {
+    // Plain register operation, continues at the next instruction.
+    Op(Code),
+    // Write of a statically known value to the IP register.
+    Jump(usize),
+    // Write of a computed value to the IP register.
+    Write(Code),
+    // Do-while loop `r[s] += r[x] if r[x] * r[y] == r[n]` for r[y] counting
+    // up until it exceeds r[n], leaving r[t] = 1 and continuing at `exit`.
+    DivisorSum {
+        x: usize,
+        y: usize,
+        t: usize,
+        n: usize,
+        s: usize,
+        exit: usize,
+    },
+    // Do-while loop running the `DivisorSum` loop (with r[y] starting at 1) for r[x]
+    // counting up until it exceeds r[n]: adds the divisors of r[n] from r[x] on to r[s],
+    // leaving r[t] = 1 and continuing at `exit`.
+    SumDivisors {
+        x: usize,
+        y: usize,
+        t: usize,
+        n: usize,
+        s: usize,
+        exit: usize,
+    },
+    // Loop incrementing r[q] until `(r[q] + 1) * k > r[d]`, i.e. integer
+    // division by k, leaving r[t] = 1 and continuing at `exit`.
+    Divide {
+        q: usize,
+        t: usize,
+        k: usize,
+        d: usize,
+        exit: usize,
+    },
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Compiled {
+    pub bound: Option<usize>,
+    pub insns: Vec<Insn>,
+}
+
+// Replaces register reads of the IP register with the instruction index.
+fn substitute(code: &Code, ip: usize, idx: usize) -> Code {
+    let (ra, rb) = code.op.reads();
+    let (a_ip, b_ip) = (ra && code.a == ip, rb && code.b == ip);
+    if !a_ip && !b_ip {
+        return *code;
+    }
+    let (a, b) = (
+        if a_ip { idx } else { code.a },
+        if b_ip { idx } else { code.b },
+    );
+    // When the IP is the only register read the result is a constant.
+    let constant = || {
+        let mut regs = vec![0; ip + 1];
+        regs[ip] = idx;
+        Code::new(Opcode::Seti, code.op.eval(code.a, code.b, &regs), 0, code.c)
+    };
+    use Opcode::*;
+    let op = match (code.op, a_ip, b_ip) {
+        (_, true, true) | (Addi, ..) | (Muli, ..) | (Bani, ..) | (Bori, ..) | (Setr, ..) => {
+            return constant()
+        }
+        (Gtri, ..) | (Eqri, ..) | (Gtir, ..) | (Eqir, ..) => return constant(),
+        (Gtrr, true, false) => return Code::new(Gtir, a, code.b, code.c),
+        (Gtrr, false, true) => return Code::new(Gtri, code.a, b, code.c),
+        (Eqrr, true, false) => return Code::new(Eqir, a, code.b, code.c),
+        (Eqrr, false, true) => return Code::new(Eqri, code.a, b, code.c),
+        (Addr, ..) => Addi,
+        (Mulr, ..) => Muli,
+        (Banr, ..) => Bani,
+        (Borr, ..) => Bori,
+        _ => unreachable!("{} does not read registers", code),
+    };
+    // Commutative operations: keep the register operand first.
+    if a_ip {
+        Code::new(op, code.b, a, code.c)
+    } else {
+        Code::new(op, code.a, b, code.c)
+    }
+}
+
+fn distinct(regs: &[usize]) -> bool {
+    (0..regs.len()).all(|i| !regs[i + 1..].contains(&regs[i]))
+}
+
+fn is(code: &Code, op: Opcode, a: usize, b: usize, c: usize) -> bool {
+    code.op == op && code.a == a && code.b == b && code.c == c
+}
+
+// Either operand order of a commutative operation.
+fn is_comm(code: &Code, op: Opcode, a: usize, b: usize, c: usize) -> bool {
+    is(code, op, a, b, c) || is(code, op, b, a, c)
+}
+
+fn jumps_to(code: &Code, ip: usize, to: usize) -> bool {
+    code.op == Opcode::Seti && code.c == ip && code.a + 1 == to
+}
+
+//  i+0: mulr x y t
+//  i+1: eqrr t n t
+//  i+2: addr t ip ip
+//  i+3: addi ip 1 ip
+//  i+4: addr x s s
+//  i+5: addi y 1 y
+//  i+6: gtrr y n t
+//  i+7: addr ip t ip
+//  i+8: seti i-1 _ ip
+fn divisor_sum(codes: &[Code], ip: usize, i: usize) -> Option<Insn> {
+    let w = codes.get(i..i + 9)?;
+    let (x, y, t) = (w[0].a, w[0].b, w[0].c);
+    let n = if w[1].a == t { w[1].b } else { w[1].a };
+    let s = w[4].c;
+    let ok = w[0].op == Opcode::Mulr
+        && distinct(&[x, y, t, n, s, ip])
+        && is_comm(&w[1], Opcode::Eqrr, t, n, t)
+        && is_comm(&w[2], Opcode::Addr, t, ip, ip)
+        && is(&w[3], Opcode::Addi, ip, 1, ip)
+        && is_comm(&w[4], Opcode::Addr, x, s, s)
+        && is(&w[5], Opcode::Addi, y, 1, y)
+        && is(&w[6], Opcode::Gtrr, y, n, t)
+        && is_comm(&w[7], Opcode::Addr, ip, t, ip)
+        && jumps_to(&w[8], ip, i);
+    if ok {
+        Some(Insn::DivisorSum {
+            x,
+            y,
+            t,
+            n,
+            s,
+            exit: i + 9,
+        })
+    } else {
+        None
+    }
+}
+
+//  i+0:  seti 1 _ y
+//  i+1:  (divisor sum loop, up to i+9)
+//  i+10: addi x 1 x
+//  i+11: gtrr x n t
+//  i+12: addr t ip ip
+//  i+13: seti i-1 _ ip
+fn sum_divisors(codes: &[Code], ip: usize, i: usize) -> Option<Insn> {
+    let w = codes.get(i..i + 14)?;
+    let (x, y, t, n, s) = match divisor_sum(codes, ip, i + 1)? {
+        Insn::DivisorSum { x, y, t, n, s, .. } => (x, y, t, n, s),
+        _ => return None,
+    };
+    let ok = w[0].op == Opcode::Seti
+        && w[0].a == 1
+        && w[0].c == y
+        && is(&w[10], Opcode::Addi, x, 1, x)
+        && is(&w[11], Opcode::Gtrr, x, n, t)
+        && is_comm(&w[12], Opcode::Addr, t, ip, ip)
+        && jumps_to(&w[13], ip, i);
+    if ok {
+        Some(Insn::SumDivisors {
+            x,
+            y,
+            t,
+            n,
+            s,
+            exit: i + 14,
+        })
+    } else {
+        None
+    }
+}
+
+// Sum of the divisors of `n` that are at least `from`, none for 0.
+pub fn divisors_from(n: usize, from: usize) -> usize {
+    let mut sum = 0;
+    let mut d = 1;
+    while d <= n / d {
+        if n.is_multiple_of(d) {
+            let e = n / d;
+            if d >= from {
+                sum += d;
+            }
+            if e != d && e >= from {
+                sum += e;
+            }
+        }
+        d += 1;
+    }
+    sum
+}
+
+//  i+0: addi q 1 t
+//  i+1: muli t k t
+//  i+2: gtrr t d t
+//  i+3: addr t ip ip
+//  i+4: addi ip 1 ip
+//  i+5: seti exit-1 _ ip
+//  i+6: addi q 1 q
+//  i+7: seti i-1 _ ip
+fn divide(codes: &[Code], ip: usize, i: usize) -> Option<Insn> {
+    let w = codes.get(i..i + 8)?;
+    let (q, t) = (w[0].a, w[0].c);
+    let (k, d) = (w[1].b, w[2].b);
+    let ok = is(&w[0], Opcode::Addi, q, 1, t)
+        && distinct(&[q, t, d, ip])
+        && k > 0
+        && is(&w[1], Opcode::Muli, t, k, t)
+        && is(&w[2], Opcode::Gtrr, t, d, t)
+        && is_comm(&w[3], Opcode::Addr, t, ip, ip)
+        && is(&w[4], Opcode::Addi, ip, 1, ip)
+        && w[5].op == Opcode::Seti
+        && w[5].c == ip
+        && is(&w[6], Opcode::Addi, q, 1, q)
+        && jumps_to(&w[7], ip, i);
+    if ok {
+        Some(Insn::Divide {
+            q,
+            t,
+            k,
+            d,
+            exit: w[5].a + 1,
+        })
+    } else {
+        None
+    }
+}
+
+impl Compiled {
+    pub fn new(program: &Program) -> Compiled {
+        let ip = match program.ip {
+            Some(ip) => ip,
+            None => {
+                return Compiled {
+                    bound: None,
+                    insns: program.codes.iter().cloned().map(Insn::Op).collect(),
+                }
+            }
+        };
+
+        let codes = &program.codes;
+        let mut insns = Vec::with_capacity(codes.len());
+        for (idx, code) in codes.iter().enumerate() {
+            let fused = sum_divisors(codes, ip, idx)
+                .or_else(|| divisor_sum(codes, ip, idx))
+                .or_else(|| divide(codes, ip, idx));
+            if let Some(insn) = fused {
+                insns.push(insn);
+                continue;
+            }
+            let code = substitute(code, ip, idx);
+            let insn = if code.c != ip {
+                Insn::Op(code)
+            } else if code.op == Opcode::Seti {
+                Insn::Jump(code.a + 1)
+            } else {
+                Insn::Write(code)
+            };
+            insns.push(insn);
+        }
+        Compiled {
+            bound: Some(ip),
+            insns,
+        }
+    }
+
+    // Same contract as `Machine::step`: returns false if the machine is halted.
+    pub fn step<const N: usize>(&self, m: &mut Machine<N>) -> bool {
+        let insn = match self.insns.get(m.ip) {
+            Some(insn) => insn,
+            None => return false,
+        };
+        let r = &mut m.regs;
+        m.ip = match *insn {
+            Insn::Op(code) => {
+                code.apply(r);
+                m.ip + 1
+            }
+            Insn::Jump(to) => to,
+            Insn::Write(code) => code.op.eval(code.a, code.b, r) + 1,
+            Insn::DivisorSum {
+                x,
+                y,
+                t,
+                n,
+                s,
+                exit,
+            } => {
+                let (from, to) = (r[y], r[y].max(r[n]));
+                let hits = match r[n].checked_div(r[x]) {
+                    Some(k) => usize::from(r[n].is_multiple_of(r[x]) && from <= k && k <= to),
+                    None if r[n] == 0 => to - from + 1,
+                    None => 0,
+                };
+                r[s] += hits * r[x];
+                r[y] = to + 1;
+                r[t] = 1;
+                exit
+            }
+            Insn::SumDivisors {
+                x,
+                y,
+                t,
+                n,
+                s,
+                exit,
+            } => {
+                r[s] += divisors_from(r[n], r[x]);
+                r[x] = r[x].max(r[n]) + 1;
+                r[y] = r[n].max(1) + 1;
+                r[t] = 1;
+                exit
+            }
+            Insn::Divide { q, t, k, d, exit } => {
+                r[q] = r[q].max(r[d] / k);
+                r[t] = 1;
+                exit
+            }
+        };
+        // The bound register holds the value last written to the IP.
+        if let Some(b) = self.bound {
+            m.regs[b] = m.ip - 1;
+        }
+        true
+    }
+
+    pub fn run<const N: usize>(&self, m: &mut Machine<N>) {
+        while self.step(m) {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elfcode::parse;
+
+    fn compare(source: &str, regs: [usize; 6]) -> Compiled {
+        let program = parse(source, 6).unwrap();
+        let mut naive = Machine::new(program.ip, regs);
+        naive.run(&program.codes);
+        let compiled = Compiled::new(&program);
+        let mut fast = Machine::new(program.ip, regs);
+        compiled.run(&mut fast);
+        assert_eq!(fast, naive);
+        compiled
+    }
+
+    // Sum of divisors of r[2], as in day 19.
+    const DIVISORS: &str = "#ip 4
+seti 1 8 1
+seti 1 3 5
+mulr 1 5 3
+eqrr 3 2 3
+addr 3 4 4
+addi 4 1 4
+addr 1 0 0
+addi 5 1 5
+gtrr 5 2 3
+addr 4 3 4
+seti 1 2 4
+addi 1 1 1
+gtrr 1 2 3
+addr 3 4 4
+seti 0 4 4";
+
+    // Divides r[5] by 256 into r[3], as in day 21.
+    const DIVIDE: &str = "#ip 4
+seti 0 0 3
+addi 3 1 1
+muli 1 256 1
+gtrr 1 5 1
+addr 1 4 4
+addi 4 1 4
+seti 8 6 4
+addi 3 1 3
+seti 0 7 4
+setr 3 4 2";
+
+    #[test]
+    fn test_divisor_sum() {
+        let compiled = compare(DIVISORS, [0, 0, 36, 0, 0, 0]);
+        match compiled.insns[2] {
+            Insn::DivisorSum {
+                x,
+                y,
+                t,
+                n,
+                s,
+                exit,
+            } => {
+                assert_eq!((x, y, t, n, s, exit), (1, 5, 3, 2, 0, 11))
+            }
+            other => panic!("not fused: {:?}", other),
+        }
+        compare(DIVISORS, [0, 0, 1, 0, 0, 0]);
+        compare(DIVISORS, [0, 0, 97, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_sum_divisors() {
+        let compiled = compare(DIVISORS, [0, 0, 36, 0, 0, 0]);
+        assert_eq!(
+            compiled.insns[1],
+            Insn::SumDivisors {
+                x: 1,
+                y: 5,
+                t: 3,
+                n: 2,
+                s: 0,
+                exit: 15
+            }
+        );
+        // Entered with r[x] already set: only the divisors from there on count.
+        let program = parse(DIVISORS, 6).unwrap();
+        for n in [0, 1, 2, 12, 49, 97, 360].iter() {
+            for x in [0, 1, 3, 7, 400].iter() {
+                let regs = [5, *x, *n, 0, 1, 0];
+                let mut naive = Machine::new(program.ip, regs);
+                naive.run(&program.codes);
+                let mut fast = Machine::new(program.ip, regs);
+                compiled.run(&mut fast);
+                assert_eq!(fast, naive, "n = {}, x = {}", n, x);
+            }
+        }
+        assert_eq!(divisors_from(36, 5), 6 + 9 + 12 + 18 + 36);
+        assert_eq!(divisors_from(0, 0), 0);
+    }
+
+    #[test]
+    fn test_divide() {
+        let compiled = compare(DIVIDE, [0, 0, 0, 0, 0, 65536]);
+        assert_eq!(
+            compiled.insns[1],
+            Insn::Divide {
+                q: 3,
+                t: 1,
+                k: 256,
+                d: 5,
+                exit: 9
+            }
+        );
+        compare(DIVIDE, [0, 0, 0, 0, 0, 0]);
+        compare(DIVIDE, [0, 0, 0, 0, 0, 1000]);
+    }
+
+    #[test]
+    fn test_substitute() {
+        let program = parse(
+            "#ip 2\nmulr 0 2 1\ngtrr 2 0 1\naddr 2 2 2\nsetr 2 0 0\neqri 2 4 1",
+            3,
+        )
+        .unwrap();
+        let compiled = compare_small(&program);
+        assert_eq!(
+            compiled.insns,
+            vec![
+                Insn::Op(Code::new(Opcode::Muli, 0, 0, 1)),
+                Insn::Op(Code::new(Opcode::Gtir, 1, 0, 1)),
+                Insn::Jump(5),
+                Insn::Op(Code::new(Opcode::Seti, 3, 0, 0)),
+                Insn::Op(Code::new(Opcode::Seti, 1, 0, 1)),
+            ]
+        );
+    }
+
+    fn compare_small(program: &Program) -> Compiled {
+        let mut naive = Machine::new(program.ip, [7, 0, 0]);
+        naive.run(&program.codes);
+        let compiled = Compiled::new(program);
+        let mut fast = Machine::new(program.ip, [7, 0, 0]);
+        compiled.run(&mut fast);
+        assert_eq!(fast, naive);
+        compiled
+    }
+}
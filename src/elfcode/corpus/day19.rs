@@ -0,0 +1,167 @@
+// Generated by elfcode::transpile, do not edit.
+pub fn day19(regs: [usize; 6]) -> [usize; 6] {
+    let [mut r0, mut r1, mut r2, mut r3, _, mut r5] = regs;
+    let mut ip: usize = 0;
+    loop {
+        match ip {
+            0 => {
+                ip = 17;
+            }
+            1 => {
+                r1 = 1;
+                ip = 2;
+            }
+            2 => {
+                let mut d = 1;
+                while d <= r2 / d {
+                    if r2.is_multiple_of(d) {
+                        let e = r2 / d;
+                        if d >= r1 {
+                            r0 += d;
+                        }
+                        if e != d && e >= r1 {
+                            r0 += e;
+                        }
+                    }
+                    d += 1;
+                }
+                r1 = r1.max(r2) + 1;
+                r5 = r2.max(1) + 1;
+                r3 = 1;
+                ip = 16;
+            }
+            3 => {
+                let (from, to) = (r5, r5.max(r2));
+                r0 += r1 * match r2.checked_div(r1) {
+                    Some(k) => usize::from(r2.is_multiple_of(r1) && from <= k && k <= to),
+                    None if r2 == 0 => to - from + 1,
+                    None => 0,
+                };
+                r5 = to + 1;
+                r3 = 1;
+                ip = 12;
+            }
+            4 => {
+                r3 = usize::from(r3 == r2);
+                ip = 5;
+            }
+            5 => {
+                ip = r3 + 5 + 1;
+            }
+            6 => {
+                ip = 8;
+            }
+            7 => {
+                r0 = r1 + r0;
+                ip = 8;
+            }
+            8 => {
+                r5 = r5 + 1;
+                ip = 9;
+            }
+            9 => {
+                r3 = usize::from(r5 > r2);
+                ip = 10;
+            }
+            10 => {
+                ip = r3 + 10 + 1;
+            }
+            11 => {
+                ip = 3;
+            }
+            12 => {
+                r1 = r1 + 1;
+                ip = 13;
+            }
+            13 => {
+                r3 = usize::from(r1 > r2);
+                ip = 14;
+            }
+            14 => {
+                ip = r3 + 14 + 1;
+            }
+            15 => {
+                ip = 2;
+            }
+            16 => {
+                ip = 257;
+            }
+            17 => {
+                r2 = r2 + 2;
+                ip = 18;
+            }
+            18 => {
+                r2 = r2 * r2;
+                ip = 19;
+            }
+            19 => {
+                r2 = r2 * 19;
+                ip = 20;
+            }
+            20 => {
+                r2 = r2 * 11;
+                ip = 21;
+            }
+            21 => {
+                r3 = r3 + 6;
+                ip = 22;
+            }
+            22 => {
+                r3 = r3 * 22;
+                ip = 23;
+            }
+            23 => {
+                r3 = r3 + 8;
+                ip = 24;
+            }
+            24 => {
+                r2 = r2 + r3;
+                ip = 25;
+            }
+            25 => {
+                ip = r0 + 25 + 1;
+            }
+            26 => {
+                ip = 1;
+            }
+            27 => {
+                r3 = 27;
+                ip = 28;
+            }
+            28 => {
+                r3 = r3 * 28;
+                ip = 29;
+            }
+            29 => {
+                r3 = r3 + 29;
+                ip = 30;
+            }
+            30 => {
+                r3 = r3 * 30;
+                ip = 31;
+            }
+            31 => {
+                r3 = r3 * 14;
+                ip = 32;
+            }
+            32 => {
+                r3 = r3 * 32;
+                ip = 33;
+            }
+            33 => {
+                r2 = r2 + r3;
+                ip = 34;
+            }
+            34 => {
+                r0 = 0;
+                ip = 35;
+            }
+            35 => {
+                ip = 1;
+            }
+            _ => break,
+        }
+    }
+    let r4 = ip - 1;
+    [r0, r1, r2, r3, r4, r5]
+}
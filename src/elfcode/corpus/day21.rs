@@ -0,0 +1,125 @@
+// Generated by elfcode::transpile, do not edit.
+pub fn day21(regs: [usize; 6]) -> [usize; 6] {
+    let [r0, mut r1, mut r2, mut r3, _, mut r5] = regs;
+    let mut ip: usize = 0;
+    loop {
+        match ip {
+            0 => {
+                r2 = 123;
+                ip = 1;
+            }
+            1 => {
+                r2 = r2 & 456;
+                ip = 2;
+            }
+            2 => {
+                r2 = usize::from(r2 == 72);
+                ip = 3;
+            }
+            3 => {
+                ip = r2 + 3 + 1;
+            }
+            4 => {
+                ip = 1;
+            }
+            5 => {
+                r2 = 0;
+                ip = 6;
+            }
+            6 => {
+                r5 = r2 | 65536;
+                ip = 7;
+            }
+            7 => {
+                r2 = 5234604;
+                ip = 8;
+            }
+            8 => {
+                r3 = r5 & 255;
+                ip = 9;
+            }
+            9 => {
+                r2 = r2 + r3;
+                ip = 10;
+            }
+            10 => {
+                r2 = r2 & 16777215;
+                ip = 11;
+            }
+            11 => {
+                r2 = r2 * 65899;
+                ip = 12;
+            }
+            12 => {
+                r2 = r2 & 16777215;
+                ip = 13;
+            }
+            13 => {
+                r3 = usize::from(256 > r5);
+                ip = 14;
+            }
+            14 => {
+                ip = r3 + 14 + 1;
+            }
+            15 => {
+                ip = 17;
+            }
+            16 => {
+                ip = 28;
+            }
+            17 => {
+                r3 = 0;
+                ip = 18;
+            }
+            18 => {
+                r3 = r3.max(r5 / 256);
+                r1 = 1;
+                ip = 26;
+            }
+            19 => {
+                r1 = r1 * 256;
+                ip = 20;
+            }
+            20 => {
+                r1 = usize::from(r1 > r5);
+                ip = 21;
+            }
+            21 => {
+                ip = r1 + 21 + 1;
+            }
+            22 => {
+                ip = 24;
+            }
+            23 => {
+                ip = 26;
+            }
+            24 => {
+                r3 = r3 + 1;
+                ip = 25;
+            }
+            25 => {
+                ip = 18;
+            }
+            26 => {
+                r5 = r3;
+                ip = 27;
+            }
+            27 => {
+                ip = 8;
+            }
+            28 => {
+                r3 = usize::from(r2 == r0);
+                ip = 29;
+            }
+            29 => {
+                ip = r3 + 29 + 1;
+            }
+            30 => {
+                ip = 6;
+            }
+            _ => break,
+        }
+    }
+    let r4 = ip - 1;
+    [r0, r1, r2, r3, r4, r5]
+}
@@ -0,0 +1,348 @@
+use std::fmt;
+use std::io::{self, Write};
+
+use super::{Code, Compiled, Machine, Program};
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Watch {
+    // Break after any instruction that changes the register.
+    Changes(usize),
+    // Break after an instruction that makes the register equal the value.
+    Equals(usize, usize),
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Stop {
+    Halted,
+    // About to execute the instruction at this index.
+    Breakpoint(usize),
+    // Watch triggered by the instruction just executed.
+    Watchpoint(Watch),
+    // Step count limit reached.
+    Limit,
+}
+
+// Single executed instruction with register file before and after it.
+// A fused loop of the optimized program is a single step at the loop head.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct Step<const N: usize> {
+    pub ip: usize,
+    pub code: Code,
+    pub before: [usize; N],
+    pub after: [usize; N],
+}
+
+impl<const N: usize> fmt::Display for Step<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "ip={} {} {:?} -> {:?}",
+            self.ip, self.code, self.before, self.after
+        )
+    }
+}
+
+pub struct Debugger<'a, const N: usize> {
+    pub machine: Machine<N>,
+    // Instructions dispatched so far: with `optimize` on, a fused loop counts
+    // as one however many instructions of the program it stands for.
+    pub steps: usize,
+    codes: &'a [Code],
+    breakpoints: Vec<bool>,
+    watches: Vec<Watch>,
+    limit: Option<usize>,
+    trace: Option<Box<dyn Write + 'a>>,
+    compiled: Option<Compiled>,
+}
+
+impl<'a, const N: usize> Debugger<'a, N> {
+    pub fn new(codes: &'a [Code], machine: Machine<N>) -> Debugger<'a, N> {
+        Debugger {
+            machine,
+            steps: 0,
+            codes,
+            breakpoints: vec![false; codes.len()],
+            watches: Vec::new(),
+            limit: None,
+            trace: None,
+            compiled: None,
+        }
+    }
+
+    pub fn breakpoint(&mut self, idx: usize) {
+        if let Some(bp) = self.breakpoints.get_mut(idx) {
+            *bp = true;
+        }
+    }
+
+    pub fn clear(&mut self, idx: usize) {
+        if let Some(bp) = self.breakpoints.get_mut(idx) {
+            *bp = false;
+        }
+    }
+
+    // Fails on a register the machine doesn't have.
+    pub fn watch(&mut self, watch: Watch) -> Result<(), String> {
+        let (Watch::Changes(r) | Watch::Equals(r, _)) = watch;
+        if r >= N {
+            return Err(format!("no register {} to watch, only {}", r, N));
+        }
+        self.watches.push(watch);
+        Ok(())
+    }
+
+    pub fn unwatch(&mut self, watch: Watch) {
+        self.watches.retain(|w| *w != watch);
+    }
+
+    // Limits total number of steps (as counted by `steps`), counting from the start.
+    pub fn limit(&mut self, steps: usize) {
+        self.limit = Some(steps);
+    }
+
+    // Writes every executed step as a line of text to the given sink,
+    // the optimized program is traced a fused loop at a time.
+    pub fn trace<W: Write + 'a>(&mut self, sink: W) {
+        self.trace = Some(Box::new(sink));
+    }
+
+    // Runs on the compiled program: breakpoints inside fused loops are not hit,
+    // and watches only see the registers before and after a whole loop.
+    pub fn optimize(&mut self) {
+        let program = Program {
+            ip: self.machine.bound,
+            codes: self.codes.to_vec(),
+        };
+        self.compiled = Some(Compiled::new(&program));
+    }
+
+    // Executes single step regardless of breakpoints and watches.
+    pub fn step(&mut self) -> io::Result<Option<Step<N>>> {
+        let ip = self.machine.ip;
+        let code = match self.codes.get(ip) {
+            Some(code) => *code,
+            None => return Ok(None),
+        };
+        let mut before = self.machine.regs;
+        if let Some(r) = self.machine.bound {
+            before[r] = ip;
+        }
+        match self.compiled.as_ref() {
+            Some(compiled) => compiled.step(&mut self.machine),
+            None => self.machine.step(self.codes),
+        };
+        self.steps += 1;
+        let step = Step {
+            ip,
+            code,
+            before,
+            after: self.machine.regs,
+        };
+        if let Some(sink) = self.trace.as_mut() {
+            writeln!(sink, "{}", step)?;
+        }
+        Ok(Some(step))
+    }
+
+    fn triggered(&self, step: &Step<N>) -> Option<Watch> {
+        self.watches.iter().cloned().find(|w| match *w {
+            Watch::Changes(r) => step.before[r] != step.after[r],
+            Watch::Equals(r, val) => step.before[r] != val && step.after[r] == val,
+        })
+    }
+
+    // Runs until the program halts or any of the stop conditions is met.
+    // A breakpoint at the current instruction is stepped over, so calling
+    // `run` again after a breakpoint continues execution.
+    pub fn run(&mut self) -> io::Result<Stop> {
+        let limit = self.limit.unwrap_or(usize::MAX);
+        if self.watches.is_empty() && self.trace.is_none() {
+            return Ok(self.run_fast(limit));
+        }
+
+        let mut first = true;
+        loop {
+            if self.steps >= limit {
+                return Ok(Stop::Limit);
+            }
+            let ip = self.machine.ip;
+            if !first && self.breakpoints.get(ip) == Some(&true) {
+                return Ok(Stop::Breakpoint(ip));
+            }
+            first = false;
+
+            let step = match self.step()? {
+                Some(step) => step,
+                None => return Ok(Stop::Halted),
+            };
+            if let Some(watch) = self.triggered(&step) {
+                return Ok(Stop::Watchpoint(watch));
+            }
+        }
+    }
+
+    // Nothing to inspect after each step, so steps are not recorded.
+    fn run_fast(&mut self, limit: usize) -> Stop {
+        let (codes, compiled) = (self.codes, self.compiled.as_ref());
+        let machine = &mut self.machine;
+        let mut first = true;
+        loop {
+            if self.steps >= limit {
+                return Stop::Limit;
+            }
+            let ip = machine.ip;
+            if !first && self.breakpoints.get(ip) == Some(&true) {
+                return Stop::Breakpoint(ip);
+            }
+            first = false;
+
+            let running = match compiled {
+                Some(compiled) => compiled.step(machine),
+                None => machine.step(codes),
+            };
+            if !running {
+                return Stop::Halted;
+            }
+            self.steps += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elfcode::parse;
+
+    // Counts r[1] up to 5, accumulating into r[0].
+    const LOOP: &str = "#ip 4
+seti 0 0 3
+addi 1 1 1
+addr 0 1 0
+eqri 1 5 2
+addr 2 4 4
+seti 0 0 4";
+
+    // Sums divisors of r[2] into r[0], the loops are fused when optimized.
+    const DIVISORS: &str = "#ip 4
+seti 1 8 1
+seti 1 3 5
+mulr 1 5 3
+eqrr 3 2 3
+addr 3 4 4
+addi 4 1 4
+addr 1 0 0
+addi 5 1 5
+gtrr 5 2 3
+addr 4 3 4
+seti 1 2 4
+addi 1 1 1
+gtrr 1 2 3
+addr 3 4 4
+seti 0 4 4";
+
+    #[test]
+    fn test_breakpoint() {
+        let program = parse(LOOP, 6).unwrap();
+        let mut dbg = Debugger::new(&program.codes, Machine::new(program.ip, [0; 6]));
+        dbg.breakpoint(3);
+        let mut seen = Vec::new();
+        loop {
+            match dbg.run().unwrap() {
+                Stop::Breakpoint(at) => {
+                    assert_eq!(at, 3);
+                    seen.push(dbg.machine.get(0));
+                }
+                Stop::Halted => break,
+                other => panic!("unexpected stop: {:?}", other),
+            }
+        }
+        assert_eq!(seen, vec![1, 3, 6, 10, 15]);
+        assert_eq!(dbg.steps, 25);
+    }
+
+    #[test]
+    fn test_watch() {
+        let program = parse(LOOP, 6).unwrap();
+        let mut dbg = Debugger::new(&program.codes, Machine::new(program.ip, [0; 6]));
+        dbg.watch(Watch::Equals(0, 6)).unwrap();
+        assert_eq!(dbg.run().unwrap(), Stop::Watchpoint(Watch::Equals(0, 6)));
+        assert_eq!(dbg.machine.ip, 3);
+        assert_eq!(dbg.machine.get(1), 3);
+
+        dbg.unwatch(Watch::Equals(0, 6));
+        dbg.watch(Watch::Changes(2)).unwrap();
+        assert_eq!(dbg.run().unwrap(), Stop::Watchpoint(Watch::Changes(2)));
+        assert_eq!(dbg.machine.get(1), 5);
+
+        assert!(dbg.watch(Watch::Changes(6)).is_err());
+        assert!(dbg.watch(Watch::Equals(9, 0)).is_err());
+        // Rejected watches aren't checked at all.
+        assert_eq!(dbg.run().unwrap(), Stop::Halted);
+    }
+
+    #[test]
+    fn test_optimize() {
+        let program = parse(LOOP, 6).unwrap();
+        let mut dbg = Debugger::new(&program.codes, Machine::new(program.ip, [0; 6]));
+        dbg.optimize();
+        dbg.breakpoint(3);
+        assert_eq!(dbg.run().unwrap(), Stop::Breakpoint(3));
+        assert_eq!(dbg.run().unwrap(), Stop::Breakpoint(3));
+        assert_eq!(dbg.machine.get(0), 3);
+        assert_eq!(dbg.run().unwrap(), Stop::Breakpoint(3));
+
+        let mut naive = Machine::new(program.ip, [0; 6]);
+        naive.run(&program.codes);
+        assert_eq!(dbg.run().unwrap(), Stop::Breakpoint(3));
+        assert_eq!(dbg.run().unwrap(), Stop::Breakpoint(3));
+        assert_eq!(dbg.run().unwrap(), Stop::Halted);
+        assert_eq!(dbg.machine, naive);
+    }
+
+    #[test]
+    fn test_limit_and_trace() {
+        let program = parse(LOOP, 6).unwrap();
+        let mut log = Vec::new();
+        {
+            let mut dbg = Debugger::new(&program.codes, Machine::new(program.ip, [0; 6]));
+            dbg.trace(&mut log);
+            dbg.limit(2);
+            assert_eq!(dbg.run().unwrap(), Stop::Limit);
+            assert_eq!(dbg.run().unwrap(), Stop::Limit);
+        }
+        let log = String::from_utf8(log).unwrap();
+        assert_eq!(
+            log,
+            "ip=0 seti 0 0 3 [0, 0, 0, 0, 0, 0] -> [0, 0, 0, 0, 0, 0]\n\
+             ip=1 addi 1 1 1 [0, 0, 0, 0, 1, 0] -> [0, 1, 0, 0, 1, 0]\n"
+        );
+    }
+
+    #[test]
+    fn test_optimized_trace() {
+        let program = parse(DIVISORS, 6).unwrap();
+        let mut log = Vec::new();
+        let steps = {
+            let mut dbg = Debugger::new(
+                &program.codes,
+                Machine::new(program.ip, [0, 0, 12, 0, 0, 0]),
+            );
+            dbg.optimize();
+            dbg.trace(&mut log);
+            assert_eq!(dbg.run().unwrap(), Stop::Halted);
+            assert_eq!(dbg.machine.get(0), 1 + 2 + 3 + 4 + 6 + 12);
+            dbg.steps
+        };
+        let log = String::from_utf8(log).unwrap();
+        assert_eq!(log.lines().count(), steps);
+
+        // The naive run dispatches every instruction of the loops.
+        let mut dbg = Debugger::new(
+            &program.codes,
+            Machine::new(program.ip, [0, 0, 12, 0, 0, 0]),
+        );
+        assert_eq!(dbg.run().unwrap(), Stop::Halted);
+        assert_eq!(dbg.machine.get(0), 28);
+        assert!(dbg.steps > 10 * steps);
+    }
+}
@@ -0,0 +1,142 @@
+use std::fmt::Write;
+
+use super::flow::{flow, leaders, Flow};
+use super::{Code, Opcode, Program};
+
+fn operand(is_reg: bool, x: usize) -> String {
+    if is_reg {
+        format!("r[{}]", x)
+    } else {
+        x.to_string()
+    }
+}
+
+// Renders a single instruction as pseudo-C, e.g. `r[2] = r[2] & 456;`.
+pub fn pseudo(code: &Code) -> String {
+    let (ra, rb) = code.op.reads();
+    let a = operand(ra, code.a);
+    let b = operand(rb, code.b);
+    let c = operand(true, code.c);
+    let binary = |sign: &str| format!("{} = {} {} {};", c, a, sign, b);
+    let compare = |sign: &str| {
+        format!(
+            "if {} {} {} {{ {} = 1; }} else {{ {} = 0; }}",
+            a, sign, b, c, c
+        )
+    };
+    match code.op {
+        Opcode::Addr | Opcode::Addi => binary("+"),
+        Opcode::Mulr | Opcode::Muli => binary("*"),
+        Opcode::Banr | Opcode::Bani => binary("&"),
+        Opcode::Borr | Opcode::Bori => binary("|"),
+        Opcode::Setr | Opcode::Seti => format!("{} = {};", c, a),
+        Opcode::Gtir | Opcode::Gtri | Opcode::Gtrr => compare(">"),
+        Opcode::Eqir | Opcode::Eqri | Opcode::Eqrr => compare("=="),
+    }
+}
+
+// Produces an annotated listing of the program: every instruction is followed
+// by its pseudo-C translation, writes to the IP register are resolved into
+// jump targets where possible and basic blocks are separated and labeled.
+pub fn disassemble(program: &Program) -> String {
+    let n = program.codes.len();
+    let width = n.saturating_sub(1).to_string().len().max(2);
+    let label = |i: usize| format!("L{:0w$}", i, w = width);
+    let blocks = leaders(program);
+
+    let mut out = String::new();
+    if let Some(ip) = program.ip {
+        writeln!(out, "#ip {}", ip).unwrap();
+    }
+    for (idx, code) in program.codes.iter().enumerate() {
+        if blocks.contains(&idx) {
+            if idx > 0 {
+                out.push('\n');
+            }
+            writeln!(out, "{}:", label(idx)).unwrap();
+        }
+        let jump = match flow(program, idx) {
+            Flow::Next => String::new(),
+            Flow::Jump(to) if to >= n => " // halt".to_string(),
+            Flow::Jump(to) => format!(" // jump to {}", label(to)),
+            Flow::Branch { reg } if idx + 2 >= n => format!(" // halt if r[{}] == 1", reg),
+            Flow::Branch { reg } => format!(" // jump to {} if r[{}] == 1", label(idx + 2), reg),
+            Flow::Offset { reg } => format!(" // jump to {} + r[{}]", label(idx + 1), reg),
+            Flow::Indirect => " // indirect jump".to_string(),
+        };
+        let text = code.to_string();
+        writeln!(
+            out,
+            "{:0w$}: {:<19} # {}{}",
+            idx,
+            text,
+            pseudo(code),
+            jump,
+            w = width
+        )
+        .unwrap();
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elfcode::parse;
+
+    #[test]
+    fn test_pseudo() {
+        let program = parse("bani 2 456 2\ngtir 256 5 3\nseti 7 8 4\nsetr 3 4 5", 6).unwrap();
+        let lines: Vec<String> = program.codes.iter().map(pseudo).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "r[2] = r[2] & 456;",
+                "if 256 > r[5] { r[3] = 1; } else { r[3] = 0; }",
+                "r[4] = 7;",
+                "r[5] = r[3];",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_disassemble() {
+        let program = parse(
+            "#ip 4
+seti 123 0 2
+bani 2 456 2
+eqri 2 72 2
+addr 2 4 4
+seti 0 0 4
+seti 0 0 2
+addi 4 1 4
+addr 4 0 4
+seti 27 2 4",
+            6,
+        )
+        .unwrap();
+        let expected = "#ip 4
+L00:
+00: seti 123 0 2        # r[2] = 123;
+
+L01:
+01: bani 2 456 2        # r[2] = r[2] & 456;
+02: eqri 2 72 2         # if r[2] == 72 { r[2] = 1; } else { r[2] = 0; }
+03: addr 2 4 4          # r[4] = r[2] + r[4]; // jump to L05 if r[2] == 1
+
+L04:
+04: seti 0 0 4          # r[4] = 0; // jump to L01
+
+L05:
+05: seti 0 0 2          # r[2] = 0;
+06: addi 4 1 4          # r[4] = r[4] + 1; // jump to L08
+
+L07:
+07: addr 4 0 4          # r[4] = r[4] + r[0]; // jump to L08 + r[0]
+
+L08:
+08: seti 27 2 4         # r[4] = 27; // halt
+";
+        assert_eq!(disassemble(&program), expected);
+    }
+}
@@ -0,0 +1,119 @@
+use std::collections::BTreeSet;
+
+use super::{Opcode, Program};
+
+// How an instruction affects the instruction pointer. Targets are the IP
+// values after the increment, i.e. the index of the next instruction to run.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Flow {
+    // Does not write the IP register.
+    Next,
+    // Writes a value known statically.
+    Jump(usize),
+    // Adds a comparison result to the IP: skips next instruction when 1.
+    Branch { reg: usize },
+    // Adds an arbitrary register to the IP: jumps to idx + 1 + r[reg].
+    Offset { reg: usize },
+    // Any other write to the IP register.
+    Indirect,
+}
+
+fn is_compare(op: Opcode) -> bool {
+    matches!(
+        op,
+        Opcode::Gtir | Opcode::Gtri | Opcode::Gtrr | Opcode::Eqir | Opcode::Eqri | Opcode::Eqrr
+    )
+}
+
+pub fn flow(program: &Program, idx: usize) -> Flow {
+    let ip = match program.ip {
+        Some(ip) => ip,
+        None => return Flow::Next,
+    };
+    let code = &program.codes[idx];
+    if code.c != ip {
+        return Flow::Next;
+    }
+
+    // Register operands are known statically only if they read the IP itself.
+    let (ra, rb) = code.op.reads();
+    let known = |is_reg: bool, x: usize| !is_reg || x == ip;
+    if known(ra, code.a) && known(rb, code.b) {
+        let mut regs = vec![0; ip + 1];
+        regs[ip] = idx;
+        return Flow::Jump(code.op.eval(code.a, code.b, &regs) + 1);
+    }
+
+    if code.op == Opcode::Addr && (code.a == ip || code.b == ip) {
+        let reg = if code.a == ip { code.b } else { code.a };
+        let prev = idx.checked_sub(1).map(|i| &program.codes[i]);
+        return match prev {
+            Some(p) if p.c == reg && is_compare(p.op) => Flow::Branch { reg },
+            _ => Flow::Offset { reg },
+        };
+    }
+
+    Flow::Indirect
+}
+
+// Instruction indices that start a basic block: the entry point, every static
+// jump target and every instruction following a write to the IP register.
+pub fn leaders(program: &Program) -> BTreeSet<usize> {
+    let n = program.codes.len();
+    let mut out = BTreeSet::new();
+    if n > 0 {
+        out.insert(0);
+    }
+    for idx in 0..n {
+        let mut add = |i: usize| {
+            if i < n {
+                out.insert(i);
+            }
+        };
+        match flow(program, idx) {
+            Flow::Next => (),
+            Flow::Jump(to) => {
+                add(to);
+                add(idx + 1);
+            }
+            Flow::Branch { .. } => {
+                add(idx + 1);
+                add(idx + 2);
+            }
+            Flow::Offset { .. } | Flow::Indirect => add(idx + 1),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elfcode::parse;
+
+    #[test]
+    fn test_flow() {
+        let program = parse(
+            "#ip 4\neqri 2 72 2\naddr 2 4 4\nseti 0 0 4\naddi 4 1 4\naddr 4 0 4\nmulr 4 4 4\nsetr 1 0 4",
+            6,
+        )
+        .unwrap();
+        let flows: Vec<Flow> = (0..program.codes.len())
+            .map(|i| flow(&program, i))
+            .collect();
+        assert_eq!(
+            flows,
+            vec![
+                Flow::Next,
+                Flow::Branch { reg: 2 },
+                Flow::Jump(1),
+                Flow::Jump(5),
+                Flow::Offset { reg: 0 },
+                Flow::Jump(26),
+                Flow::Indirect,
+            ]
+        );
+        let leaders: Vec<usize> = leaders(&program).into_iter().collect();
+        assert_eq!(leaders, vec![0, 1, 2, 3, 4, 5, 6]);
+    }
+}
@@ -0,0 +1,409 @@
+use std::fmt;
+
+use super::{Code, Machine, Opcode};
+
+// Instruction with an opcode number whose meaning is not known yet.
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+pub struct RawCode {
+    pub op: usize,
+    pub a: usize,
+    pub b: usize,
+    pub c: usize,
+}
+
+impl RawCode {
+    pub fn new(op: usize, a: usize, b: usize, c: usize) -> RawCode {
+        RawCode { op, a, b, c }
+    }
+
+    pub fn with(&self, op: Opcode) -> Code {
+        Code::new(op, self.a, self.b, self.c)
+    }
+}
+
+// Observed effect of a single instruction on the register file.
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+pub struct Record {
+    pub was: [usize; 4],
+    pub code: RawCode,
+    pub now: [usize; 4],
+}
+
+impl Record {
+    pub fn fits(&self, op: Opcode) -> bool {
+        let code = self.code.with(op);
+        let regs = &self.was;
+        let (ra, rb) = op.reads();
+        // Out-of-range register operands can't explain the sample.
+        if code.c >= 4 || (ra && code.a >= 4) || (rb && code.b >= 4) {
+            return false;
+        }
+        let mut state = Machine::new(None, *regs);
+        state.exec(&code);
+        state.regs == self.now
+    }
+
+    pub fn candidates(&self) -> Vec<Opcode> {
+        Opcode::ALL
+            .iter()
+            .cloned()
+            .filter(|op| self.fits(*op))
+            .collect()
+    }
+}
+
+// Meaning of every opcode number: mapping[number] => opcode.
+pub type Mapping = [Opcode; 16];
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum Conflict {
+    // No opcode fits all the samples with this number: every opcode comes
+    // with the index of the first sample that ruled it out.
+    Empty {
+        number: usize,
+        ruled_out: Vec<(Opcode, usize)>,
+    },
+    // Every opcode fits some number, but these numbers can only be mapped
+    // to fewer distinct opcodes than there are numbers.
+    Crowded {
+        numbers: Vec<usize>,
+        opcodes: Vec<Opcode>,
+    },
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Conflict::Empty { number, ruled_out } => {
+                write!(f, "no opcode fits number {}:", number)?;
+                for (op, sample) in ruled_out {
+                    write!(f, " {} (sample {})", op, sample)?;
+                }
+                Ok(())
+            }
+            Conflict::Crowded { numbers, opcodes } => {
+                let opcodes: Vec<&str> = opcodes.iter().map(|op| op.name()).collect();
+                write!(
+                    f,
+                    "numbers {:?} only fit opcodes [{}]",
+                    numbers,
+                    opcodes.join(", ")
+                )
+            }
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum Inference {
+    Unique(Mapping),
+    // More samples are required to tell these mappings apart. Enumeration
+    // stops at the limit given to `solve`, `complete` tells if it did.
+    Ambiguous {
+        mappings: Vec<Mapping>,
+        complete: bool,
+    },
+    Inconsistent(Conflict),
+}
+
+// Candidate opcodes of every number as bit sets over `Opcode::ALL`.
+pub struct Solver {
+    fits: [u16; 16],
+    ruled_out: [[Option<usize>; 16]; 16],
+}
+
+const ALL: u16 = 0xFFFF;
+
+fn opcodes(set: u16) -> Vec<Opcode> {
+    (0..16)
+        .filter(|i| set & (1 << i) != 0)
+        .map(|i| Opcode::ALL[i])
+        .collect()
+}
+
+impl Solver {
+    pub fn new(records: &[Record]) -> Solver {
+        let mut fits = [ALL; 16];
+        let mut ruled_out = [[None; 16]; 16];
+        for (idx, rec) in records.iter().enumerate() {
+            let n = rec.code.op;
+            // `Samples::parse` rejects such numbers, there's nothing to learn from them.
+            if n >= 16 {
+                continue;
+            }
+            for (i, op) in Opcode::ALL.iter().enumerate() {
+                if ruled_out[n][i].is_none() && !rec.fits(*op) {
+                    ruled_out[n][i] = Some(idx);
+                    fits[n] &= !(1 << i);
+                }
+            }
+        }
+        Solver { fits, ruled_out }
+    }
+
+    pub fn candidates(&self, number: usize) -> Vec<Opcode> {
+        opcodes(self.fits[number])
+    }
+
+    // Index of the first sample showing that number can't be the opcode.
+    pub fn ruled_out_by(&self, number: usize, op: Opcode) -> Option<usize> {
+        self.ruled_out[number][op as usize]
+    }
+
+    // Finds up to `limit` mappings consistent with all samples: propagates
+    // constraints and backtracks on the most constrained number when stuck.
+    // A search cut short before finding anything is reported as ambiguous
+    // (and incomplete), only an exhausted one proves the samples inconsistent.
+    pub fn solve(&self, limit: usize) -> Inference {
+        if let Some(conflict) = self.empty() {
+            return Inference::Inconsistent(conflict);
+        }
+        let mut found = Vec::new();
+        let complete = search(self.fits, limit, &mut found);
+        match (found.len(), complete) {
+            (0, true) => Inference::Inconsistent(self.crowded()),
+            (1, true) => Inference::Unique(found[0]),
+            _ => Inference::Ambiguous {
+                mappings: found,
+                complete,
+            },
+        }
+    }
+
+    fn empty(&self) -> Option<Conflict> {
+        let number = (0..16).find(|n| self.fits[*n] == 0)?;
+        let ruled_out = Opcode::ALL
+            .iter()
+            .map(|op| (*op, self.ruled_out_by(number, *op).unwrap()))
+            .collect();
+        Some(Conflict::Empty { number, ruled_out })
+    }
+
+    // With no one-to-one assignment possible there is a subset of numbers
+    // covering fewer opcodes than its size (Hall's theorem): the smallest one.
+    fn crowded(&self) -> Conflict {
+        (1u32..(1 << 16))
+            .filter_map(|subset| {
+                let numbers: Vec<usize> = (0..16).filter(|n| subset & (1 << n) != 0).collect();
+                let union = numbers.iter().fold(0, |acc, n| acc | self.fits[*n]);
+                if (union.count_ones() as usize) < numbers.len() {
+                    Some((numbers, union))
+                } else {
+                    None
+                }
+            })
+            .min_by_key(|(numbers, _)| numbers.len())
+            .map(|(numbers, union)| Conflict::Crowded {
+                numbers,
+                opcodes: opcodes(union),
+            })
+            .unwrap()
+    }
+}
+
+// Removes opcodes fixed to one number from all others, and fixes opcodes
+// fitting only one number. Returns false when some number runs out of options.
+fn propagate(fits: &mut [u16; 16]) -> bool {
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for n in 0..16 {
+            if fits[n] == 0 {
+                return false;
+            }
+            if fits[n].count_ones() == 1 {
+                for m in 0..16 {
+                    if m != n && fits[m] & fits[n] != 0 {
+                        fits[m] &= !fits[n];
+                        changed = true;
+                    }
+                }
+            }
+        }
+        for i in 0..16 {
+            let bit = 1 << i;
+            let owners: Vec<usize> = (0..16).filter(|n| fits[*n] & bit != 0).collect();
+            if owners.len() == 1 && fits[owners[0]] != bit {
+                fits[owners[0]] = bit;
+                changed = true;
+            }
+        }
+    }
+    true
+}
+
+// Returns false if the search was cut short by the limit.
+fn search(mut fits: [u16; 16], limit: usize, found: &mut Vec<Mapping>) -> bool {
+    if !propagate(&mut fits) {
+        return true;
+    }
+    let open = (0..16)
+        .filter(|n| fits[*n].count_ones() > 1)
+        .min_by_key(|n| fits[*n].count_ones());
+    let number = match open {
+        Some(number) => number,
+        None => {
+            if found.len() == limit {
+                return false;
+            }
+            let mut mapping = [Opcode::Addr; 16];
+            for (n, set) in fits.iter().enumerate() {
+                mapping[n] = Opcode::ALL[set.trailing_zeros() as usize];
+            }
+            found.push(mapping);
+            return true;
+        }
+    };
+    for i in 0..16 {
+        if fits[number] & (1 << i) != 0 {
+            let mut next = fits;
+            next[number] = 1 << i;
+            if !search(next, limit, found) {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(was: [usize; 4], code: [usize; 4], now: [usize; 4]) -> Record {
+        Record {
+            was,
+            code: RawCode::new(code[0], code[1], code[2], code[3]),
+            now,
+        }
+    }
+
+    #[test]
+    fn test_candidates() {
+        let rec = sample([3, 2, 1, 1], [9, 2, 1, 2], [3, 2, 2, 1]);
+        assert_eq!(
+            rec.candidates(),
+            vec![Opcode::Addi, Opcode::Mulr, Opcode::Seti]
+        );
+    }
+
+    #[test]
+    fn test_unsolvable_with_no_samples() {
+        match Solver::new(&[]).solve(10) {
+            Inference::Ambiguous { mappings, complete } => {
+                assert_eq!(mappings.len(), 10);
+                assert!(!complete);
+            }
+            other => panic!("unexpected: {:?}", other),
+        }
+    }
+
+    // Runs every opcode under its own number on pseudo-random register
+    // files and operands, enough to pin down the identity mapping.
+    fn identity_samples() -> Vec<Record> {
+        let mut seed: usize = 42;
+        let mut next = |m: usize| {
+            seed = (seed * 1103515245 + 12345) % (1 << 31);
+            (seed >> 16) % m
+        };
+        let mut out = Vec::new();
+        for _ in 0..8 {
+            for (n, op) in Opcode::ALL.iter().enumerate() {
+                let was = [next(16), next(16), next(16), next(16)];
+                let code = RawCode::new(n, next(4), next(4), next(4));
+                let mut m = Machine::new(None, was);
+                m.exec(&code.with(*op));
+                out.push(Record {
+                    was,
+                    code,
+                    now: m.regs,
+                });
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn test_unique() {
+        assert_eq!(
+            Solver::new(&identity_samples()).solve(10),
+            Inference::Unique(Opcode::ALL)
+        );
+    }
+
+    #[test]
+    fn test_no_limit() {
+        // Nothing can be found without room for a mapping, which proves nothing.
+        assert_eq!(
+            Solver::new(&identity_samples()).solve(0),
+            Inference::Ambiguous {
+                mappings: vec![],
+                complete: false
+            }
+        );
+        if let Ok(input) = std::fs::read_to_string("inputs/day16.txt") {
+            let samples = crate::elfcode::samples::Samples::parse(&input).unwrap();
+            assert_eq!(
+                Solver::new(&samples.records).solve(0),
+                Inference::Ambiguous {
+                    mappings: vec![],
+                    complete: false
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn test_ambiguous() {
+        // Numbers 1 and 9 have no samples, so addi and seti may be swapped.
+        let samples: Vec<Record> = identity_samples()
+            .into_iter()
+            .filter(|rec| rec.code.op != 1 && rec.code.op != 9)
+            .collect();
+        match Solver::new(&samples).solve(usize::MAX) {
+            Inference::Ambiguous { mappings, complete } => {
+                assert!(complete);
+                assert_eq!(mappings.len(), 2);
+                assert!(mappings.contains(&Opcode::ALL));
+            }
+            other => panic!("unexpected: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_empty_conflict() {
+        let samples = vec![
+            sample([3, 2, 1, 1], [0, 2, 1, 2], [3, 2, 2, 1]),
+            sample([0, 0, 0, 0], [0, 0, 0, 0], [1, 1, 1, 1]),
+        ];
+        match Solver::new(&samples).solve(1) {
+            Inference::Inconsistent(Conflict::Empty { number, ruled_out }) => {
+                assert_eq!(number, 0);
+                assert_eq!(ruled_out.len(), 16);
+                assert!(ruled_out.contains(&(Opcode::Mulr, 1)));
+                assert!(ruled_out.contains(&(Opcode::Banr, 0)));
+            }
+            other => panic!("unexpected: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_crowded_conflict() {
+        // Both 0 and 1 can only be seti.
+        let samples = vec![
+            sample([0, 0, 0, 0], [0, 7, 0, 0], [7, 0, 0, 0]),
+            sample([0, 0, 0, 0], [1, 7, 0, 0], [7, 0, 0, 0]),
+            sample([1, 2, 3, 4], [0, 7, 0, 0], [7, 2, 3, 4]),
+            sample([1, 2, 3, 4], [1, 7, 0, 0], [7, 2, 3, 4]),
+        ];
+        let solver = Solver::new(&samples);
+        assert_eq!(solver.candidates(0), vec![Opcode::Seti]);
+        assert_eq!(
+            solver.solve(1),
+            Inference::Inconsistent(Conflict::Crowded {
+                numbers: vec![0, 1],
+                opcodes: vec![Opcode::Seti],
+            })
+        );
+    }
+}
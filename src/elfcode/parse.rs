@@ -0,0 +1,235 @@
+use std::error::Error;
+use std::fmt;
+
+use super::{Code, Opcode, Program};
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum ErrorKind {
+    MissingOperand,
+    InvalidOperand,
+    UnknownOpcode,
+    UnexpectedToken,
+    DuplicateIp,
+    MisplacedIp,
+    RegisterOutOfRange,
+}
+
+impl ErrorKind {
+    fn describe(self) -> &'static str {
+        match self {
+            ErrorKind::MissingOperand => "missing operand",
+            ErrorKind::InvalidOperand => "operand is not a number",
+            ErrorKind::UnknownOpcode => "unknown opcode",
+            ErrorKind::UnexpectedToken => "unexpected token",
+            ErrorKind::DuplicateIp => "duplicate #ip directive",
+            ErrorKind::MisplacedIp => "#ip directive after first instruction",
+            ErrorKind::RegisterOutOfRange => "register index out of range",
+        }
+    }
+}
+
+// Line and column are 1-based, column points at the offending token
+// (or just past the end of the line for a missing operand).
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ParseError {
+    pub kind: ErrorKind,
+    pub line: usize,
+    pub col: usize,
+    pub token: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.col, self.kind.describe())?;
+        if !self.token.is_empty() {
+            write!(f, " '{}'", self.token)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ParseError {}
+
+struct Line<'a> {
+    number: usize,
+    text: &'a str,
+    tokens: Vec<(usize, &'a str)>,
+    next: usize,
+}
+
+impl<'a> Line<'a> {
+    fn new(number: usize, text: &'a str) -> Line<'a> {
+        let mut tokens = Vec::new();
+        let mut start = None;
+        for (i, c) in text.char_indices() {
+            match (c.is_whitespace(), start) {
+                (false, None) => start = Some(i),
+                (true, Some(s)) => {
+                    tokens.push((s, &text[s..i]));
+                    start = None;
+                }
+                _ => (),
+            }
+        }
+        if let Some(s) = start {
+            tokens.push((s, &text[s..]));
+        }
+        Line {
+            number,
+            text,
+            tokens,
+            next: 0,
+        }
+    }
+
+    fn is_blank(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    fn error(&self, kind: ErrorKind, at: usize, token: &str) -> ParseError {
+        ParseError {
+            kind,
+            line: self.number,
+            col: self.text[..at].chars().count() + 1,
+            token: token.to_string(),
+        }
+    }
+
+    fn head(&mut self) -> (usize, &'a str) {
+        self.next = 1;
+        self.tokens[0]
+    }
+
+    fn number(&mut self) -> Result<(usize, usize), ParseError> {
+        let (at, token) = match self.tokens.get(self.next) {
+            Some(t) => *t,
+            None => {
+                let end = self.text.trim_end().len();
+                return Err(self.error(ErrorKind::MissingOperand, end, ""));
+            }
+        };
+        self.next += 1;
+        token
+            .parse()
+            .map(|n| (at, n))
+            .map_err(|_| self.error(ErrorKind::InvalidOperand, at, token))
+    }
+
+    fn register(&mut self, registers: usize) -> Result<usize, ParseError> {
+        let (at, n) = self.number()?;
+        if n >= registers {
+            let token = self.tokens[self.next - 1].1;
+            return Err(self.error(ErrorKind::RegisterOutOfRange, at, token));
+        }
+        Ok(n)
+    }
+
+    fn operand(&mut self, is_register: bool, registers: usize) -> Result<usize, ParseError> {
+        if is_register {
+            self.register(registers)
+        } else {
+            self.number().map(|(_, n)| n)
+        }
+    }
+
+    fn end(&self) -> Result<(), ParseError> {
+        match self.tokens.get(self.next) {
+            Some((at, token)) => Err(self.error(ErrorKind::UnexpectedToken, *at, token)),
+            None => Ok(()),
+        }
+    }
+}
+
+// Parses ElfCode source for a machine with given number of registers.
+// Blank lines are skipped, `#ip` may only appear once, before any instruction.
+pub fn parse(input: &str, registers: usize) -> Result<Program, ParseError> {
+    let mut ip = None;
+    let mut codes = Vec::new();
+
+    for (idx, text) in input.lines().enumerate() {
+        let mut line = Line::new(idx + 1, text);
+        if line.is_blank() {
+            continue;
+        }
+
+        let (at, head) = line.head();
+        if head == "#ip" {
+            if ip.is_some() {
+                return Err(line.error(ErrorKind::DuplicateIp, at, head));
+            }
+            if !codes.is_empty() {
+                return Err(line.error(ErrorKind::MisplacedIp, at, head));
+            }
+            ip = Some(line.register(registers)?);
+            line.end()?;
+            continue;
+        }
+
+        let op = match Opcode::from_name(head) {
+            Some(op) => op,
+            None => return Err(line.error(ErrorKind::UnknownOpcode, at, head)),
+        };
+        let (ra, rb) = op.reads();
+        let a = line.operand(ra, registers)?;
+        let b = line.operand(rb, registers)?;
+        let c = line.register(registers)?;
+        line.end()?;
+        codes.push(Code::new(op, a, b, c));
+    }
+
+    Ok(Program { ip, codes })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fail(input: &str) -> (ErrorKind, usize, usize, String) {
+        let e = parse(input, 6).unwrap_err();
+        (e.kind, e.line, e.col, e.token)
+    }
+
+    #[test]
+    fn test_parse() {
+        let program = parse("#ip 0\nseti 5 0 1\n\r\n  addr 1 2 3  \r\n", 6).unwrap();
+        assert_eq!(program.ip, Some(0));
+        assert_eq!(
+            program.codes,
+            vec![
+                Code::new(Opcode::Seti, 5, 0, 1),
+                Code::new(Opcode::Addr, 1, 2, 3),
+            ]
+        );
+
+        let program = parse("gtir 256 5 3", 6).unwrap();
+        assert_eq!(program.ip, None);
+        assert_eq!(program.codes, vec![Code::new(Opcode::Gtir, 256, 5, 3)]);
+    }
+
+    #[test]
+    fn test_errors() {
+        use ErrorKind::*;
+        assert_eq!(fail("seti 5 0"), (MissingOperand, 1, 9, "".into()));
+        assert_eq!(
+            fail("#ip 0\nseti 5 x 1"),
+            (InvalidOperand, 2, 8, "x".into())
+        );
+        assert_eq!(
+            fail("seti 5 0 1\nsetx 1 2 3"),
+            (UnknownOpcode, 2, 1, "setx".into())
+        );
+        assert_eq!(fail("seti 5 0 1 2"), (UnexpectedToken, 1, 12, "2".into()));
+        assert_eq!(fail("#ip 0\n#ip 1"), (DuplicateIp, 2, 1, "#ip".into()));
+        assert_eq!(fail("seti 5 0 1\n#ip 1"), (MisplacedIp, 2, 1, "#ip".into()));
+        assert_eq!(fail("#ip 6"), (RegisterOutOfRange, 1, 5, "6".into()));
+        assert_eq!(fail("addr 1 7 3"), (RegisterOutOfRange, 1, 8, "7".into()));
+        assert_eq!(fail("seti 7 0 9"), (RegisterOutOfRange, 1, 10, "9".into()));
+        assert_eq!(fail("#ip"), (MissingOperand, 1, 4, "".into()));
+    }
+
+    #[test]
+    fn test_display() {
+        let e = parse("nop 1 2 3", 6).unwrap_err();
+        assert_eq!(e.to_string(), "1:1: unknown opcode 'nop'");
+    }
+}
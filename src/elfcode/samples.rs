@@ -0,0 +1,217 @@
+use std::error::Error;
+use std::fmt;
+
+use super::infer::{RawCode, Record};
+
+// Puzzle input of day 16: instruction samples followed by a program, both
+// with unknown opcode numbers.
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct Samples {
+    pub records: Vec<Record>,
+    pub program: Vec<RawCode>,
+}
+
+// Line is 1-based, `found` holds the trimmed offending line.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct SampleError {
+    pub line: usize,
+    pub expected: &'static str,
+    pub found: String,
+}
+
+impl fmt::Display for SampleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}: expected {}, found '{}'",
+            self.line, self.expected, self.found
+        )
+    }
+}
+
+impl Error for SampleError {}
+
+const BEFORE: &str = "'Before: [a, b, c, d]'";
+const AFTER: &str = "'After: [a, b, c, d]'";
+const CODE: &str = "'op a b c'";
+const OPCODE: &str = "an opcode number below 16";
+
+fn numbers<'a, I>(parts: I) -> impl Iterator<Item = Option<usize>> + 'a
+where
+    I: Iterator<Item = &'a str> + 'a,
+{
+    parts.map(|s| s.trim().parse().ok())
+}
+
+fn four<I: Iterator<Item = Option<usize>>>(mut it: I) -> Option<[usize; 4]> {
+    let out = [it.next()??, it.next()??, it.next()??, it.next()??];
+    match it.next() {
+        None => Some(out),
+        Some(_) => None,
+    }
+}
+
+fn registers(line: &str, prefix: &str) -> Option<[usize; 4]> {
+    let rest = line.strip_prefix(prefix)?.trim();
+    let inner = rest.strip_prefix('[')?.strip_suffix(']')?;
+    four(numbers(inner.split(',')))
+}
+
+fn code(line: &str) -> Option<RawCode> {
+    four(numbers(line.split_whitespace())).map(|[op, a, b, c]| RawCode::new(op, a, b, c))
+}
+
+// There are only 16 opcodes, so larger numbers can't be decoded.
+fn opcode(n: usize, line: &str, code: RawCode) -> Result<RawCode, SampleError> {
+    if code.op < 16 {
+        Ok(code)
+    } else {
+        Err(SampleError {
+            line: n,
+            expected: OPCODE,
+            found: line.to_string(),
+        })
+    }
+}
+
+impl Samples {
+    // Tolerates surrounding whitespace, CRLF line endings and any number of
+    // blank lines between blocks. Anything after the first line that does
+    // not start a sample is the program.
+    pub fn parse(input: &str) -> Result<Samples, SampleError> {
+        let mut lines = input
+            .lines()
+            .map(str::trim)
+            .enumerate()
+            .map(|(i, line)| (i + 1, line))
+            .filter(|(_, line)| !line.is_empty())
+            .peekable();
+        let fail = |line: usize, expected: &'static str, found: &str| SampleError {
+            line,
+            expected,
+            found: found.to_string(),
+        };
+
+        let mut out = Samples::default();
+        while let Some((n, line)) = lines.next_if(|(_, line)| line.starts_with("Before:")) {
+            let was = registers(line, "Before:").ok_or_else(|| fail(n, BEFORE, line))?;
+            let (n, line) = lines.next().ok_or_else(|| fail(n + 1, CODE, ""))?;
+            let code = opcode(n, line, code(line).ok_or_else(|| fail(n, CODE, line))?)?;
+            let (n, line) = lines.next().ok_or_else(|| fail(n + 1, AFTER, ""))?;
+            let now = registers(line, "After:").ok_or_else(|| fail(n, AFTER, line))?;
+            out.records.push(Record { was, code, now });
+        }
+        for (n, line) in lines {
+            let code = code(line).ok_or_else(|| fail(n, CODE, line))?;
+            out.program.push(opcode(n, line, code)?);
+        }
+        Ok(out)
+    }
+}
+
+fn regs(r: &[usize; 4]) -> String {
+    format!("[{}, {}, {}, {}]", r[0], r[1], r[2], r[3])
+}
+
+// Writes the samples back in the puzzle format.
+impl fmt::Display for Samples {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, rec) in self.records.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            let c = &rec.code;
+            writeln!(f, "Before: {}", regs(&rec.was))?;
+            writeln!(f, "{} {} {} {}", c.op, c.a, c.b, c.c)?;
+            writeln!(f, "After:  {}", regs(&rec.now))?;
+        }
+        if !self.records.is_empty() && !self.program.is_empty() {
+            write!(f, "\n\n\n")?;
+        }
+        for c in &self.program {
+            writeln!(f, "{} {} {} {}", c.op, c.a, c.b, c.c)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "Before: [3, 2, 1, 1]
+9 2 1 2
+After:  [3, 2, 2, 1]
+
+Before: [0, 1, 2, 3]
+4 0 3 1
+After:  [0, 3, 2, 3]
+
+
+
+7 3 2 0
+1 0 0 3
+";
+
+    #[test]
+    fn test_round_trip() {
+        let samples = Samples::parse(INPUT).unwrap();
+        assert_eq!(samples.records.len(), 2);
+        assert_eq!(samples.records[1].was, [0, 1, 2, 3]);
+        assert_eq!(samples.records[1].code, RawCode::new(4, 0, 3, 1));
+        assert_eq!(samples.records[1].now, [0, 3, 2, 3]);
+        assert_eq!(
+            samples.program,
+            vec![RawCode::new(7, 3, 2, 0), RawCode::new(1, 0, 0, 3)]
+        );
+        assert_eq!(samples.to_string(), INPUT);
+    }
+
+    #[test]
+    fn test_tolerant() {
+        let messy =
+            "Before:[3,2, 1,1] \r\n9  2 1 2\r\nAfter: [3, 2, 2, 1]\r\n\r\n \r\n7 3 2 0\r\n1 0 0 3";
+        let samples = Samples::parse(messy).unwrap();
+        let clean = Samples::parse(INPUT).unwrap();
+        assert_eq!(samples.records, clean.records[..1].to_vec());
+        assert_eq!(samples.program, clean.program);
+
+        let spaced = "Before: [3, 2, 1, 1]\n9\t2 \t1   2\nAfter:  [3, 2, 2, 1]\n\n\n\n7\t3\t2\t0\n 1  0  0  3 ";
+        let samples = Samples::parse(spaced).unwrap();
+        assert_eq!(samples.records, clean.records[..1].to_vec());
+        assert_eq!(samples.program, clean.program);
+    }
+
+    #[test]
+    fn test_errors() {
+        let err = |input: &str| {
+            let e = Samples::parse(input).unwrap_err();
+            (e.line, e.expected, e.found)
+        };
+        assert_eq!(
+            err("Before: [3, 2, 1]\n9 2 1 2\nAfter: [3, 2, 2, 1]"),
+            (1, BEFORE, "Before: [3, 2, 1]".to_string())
+        );
+        assert_eq!(
+            err("Before: [3, 2, 1, 1]\n9 2 x 2\nAfter: [3, 2, 2, 1]"),
+            (2, CODE, "9 2 x 2".to_string())
+        );
+        assert_eq!(
+            err("Before: [3, 2, 1, 1]\n9 2 1 2\n\nAfter [3, 2, 2, 1]"),
+            (4, AFTER, "After [3, 2, 2, 1]".to_string())
+        );
+        assert_eq!(
+            err("Before: [3, 2, 1, 1]\n9 2 1 2"),
+            (3, AFTER, "".to_string())
+        );
+        assert_eq!(err("\n\n7 3 2 0 1"), (3, CODE, "7 3 2 0 1".to_string()));
+        assert_eq!(
+            err("Before: [3, 2, 1, 1]\n16 2 1 2\nAfter: [3, 2, 2, 1]"),
+            (2, OPCODE, "16 2 1 2".to_string())
+        );
+        assert_eq!(
+            err("7 3 2 0\n21 0 0 3"),
+            (2, OPCODE, "21 0 0 3".to_string())
+        );
+    }
+}
@@ -0,0 +1,235 @@
+use std::fmt::Write;
+
+use super::{Code, Compiled, Insn, Opcode, Program};
+
+fn reg(r: usize) -> String {
+    format!("r{}", r)
+}
+
+fn expr(code: &Code) -> String {
+    let (ra, rb) = code.op.reads();
+    let a = if ra { reg(code.a) } else { code.a.to_string() };
+    let b = if rb { reg(code.b) } else { code.b.to_string() };
+    match code.op {
+        Opcode::Addr | Opcode::Addi => format!("{} + {}", a, b),
+        Opcode::Mulr | Opcode::Muli => format!("{} * {}", a, b),
+        Opcode::Banr | Opcode::Bani => format!("{} & {}", a, b),
+        Opcode::Borr | Opcode::Bori => format!("{} | {}", a, b),
+        Opcode::Setr | Opcode::Seti => a,
+        Opcode::Gtir | Opcode::Gtri | Opcode::Gtrr => format!("usize::from({} > {})", a, b),
+        Opcode::Eqir | Opcode::Eqri | Opcode::Eqrr => format!("usize::from({} == {})", a, b),
+    }
+}
+
+fn body(insn: &Insn, idx: usize) -> Vec<String> {
+    match *insn {
+        Insn::Op(code) => vec![
+            format!("{} = {};", reg(code.c), expr(&code)),
+            format!("ip = {};", idx + 1),
+        ],
+        Insn::Jump(to) => vec![format!("ip = {};", to)],
+        Insn::Write(code) => vec![format!("ip = {} + 1;", expr(&code))],
+        Insn::DivisorSum {
+            x,
+            y,
+            t,
+            n,
+            s,
+            exit,
+        } => {
+            let (x, y, t, n, s) = (reg(x), reg(y), reg(t), reg(n), reg(s));
+            vec![
+                format!("let (from, to) = ({}, {}.max({}));", y, y, n),
+                format!("{} += {} * match {}.checked_div({}) {{", s, x, n, x),
+                format!(
+                    "    Some(k) => usize::from({}.is_multiple_of({}) && from <= k && k <= to),",
+                    n, x
+                ),
+                format!("    None if {} == 0 => to - from + 1,", n),
+                "    None => 0,".to_string(),
+                "};".to_string(),
+                format!("{} = to + 1;", y),
+                format!("{} = 1;", t),
+                format!("ip = {};", exit),
+            ]
+        }
+        Insn::SumDivisors {
+            x,
+            y,
+            t,
+            n,
+            s,
+            exit,
+        } => {
+            let (x, y, t, n, s) = (reg(x), reg(y), reg(t), reg(n), reg(s));
+            vec![
+                "let mut d = 1;".to_string(),
+                format!("while d <= {} / d {{", n),
+                format!("    if {}.is_multiple_of(d) {{", n),
+                format!("        let e = {} / d;", n),
+                format!("        if d >= {} {{", x),
+                format!("            {} += d;", s),
+                "        }".to_string(),
+                format!("        if e != d && e >= {} {{", x),
+                format!("            {} += e;", s),
+                "        }".to_string(),
+                "    }".to_string(),
+                "    d += 1;".to_string(),
+                "}".to_string(),
+                format!("{} = {}.max({}) + 1;", x, x, n),
+                format!("{} = {}.max(1) + 1;", y, n),
+                format!("{} = 1;", t),
+                format!("ip = {};", exit),
+            ]
+        }
+        Insn::Divide { q, t, k, d, exit } => vec![
+            format!("{} = {}.max({} / {});", reg(q), reg(q), reg(d), k),
+            format!("{} = 1;", reg(t)),
+            format!("ip = {};", exit),
+        ],
+    }
+}
+
+fn writes(insn: &Insn) -> Vec<usize> {
+    match *insn {
+        Insn::Op(code) => vec![code.c],
+        Insn::Jump(_) | Insn::Write(_) => vec![],
+        Insn::DivisorSum { y, t, s, .. } => vec![y, t, s],
+        Insn::SumDivisors { x, y, t, s, .. } => vec![x, y, t, s],
+        Insn::Divide { q, t, .. } => vec![q, t],
+    }
+}
+
+// Generates a standalone Rust function running the program on the given
+// register file: a `loop` over a `match` on the IP with an arm for each
+// (compiled) instruction, and registers kept in local variables.
+pub fn transpile(program: &Program, name: &str, registers: usize) -> String {
+    let compiled = Compiled::new(program);
+    let written: Vec<usize> = compiled.insns.iter().flat_map(writes).collect();
+    // Initial value of the bound register is never read, it is only set
+    // from the IP once the program halts.
+    let bound = compiled.bound.filter(|_| !compiled.insns.is_empty());
+
+    let regs: Vec<String> = (0..registers).map(reg).collect();
+    let bindings: Vec<String> = (0..registers)
+        .map(|r| {
+            if bound == Some(r) {
+                "_".to_string()
+            } else if written.contains(&r) {
+                format!("mut {}", reg(r))
+            } else {
+                reg(r)
+            }
+        })
+        .collect();
+
+    let mut out = String::new();
+    writeln!(out, "// Generated by elfcode::transpile, do not edit.").unwrap();
+    writeln!(
+        out,
+        "pub fn {}(regs: [usize; {}]) -> [usize; {}] {{",
+        name, registers, registers
+    )
+    .unwrap();
+    writeln!(out, "    let [{}] = regs;", bindings.join(", ")).unwrap();
+    if compiled.insns.is_empty() {
+        writeln!(out, "    [{}]", regs.join(", ")).unwrap();
+        writeln!(out, "}}").unwrap();
+        return out;
+    }
+
+    writeln!(out, "    let mut ip: usize = 0;").unwrap();
+    writeln!(out, "    loop {{").unwrap();
+    writeln!(out, "        match ip {{").unwrap();
+    for (idx, insn) in compiled.insns.iter().enumerate() {
+        writeln!(out, "            {} => {{", idx).unwrap();
+        for line in body(insn, idx) {
+            writeln!(out, "                {}", line).unwrap();
+        }
+        writeln!(out, "            }}").unwrap();
+    }
+    writeln!(out, "            _ => break,").unwrap();
+    writeln!(out, "        }}").unwrap();
+    writeln!(out, "    }}").unwrap();
+    if let Some(b) = bound {
+        writeln!(out, "    let {} = ip - 1;", reg(b)).unwrap();
+    }
+    writeln!(out, "    [{}]", regs.join(", ")).unwrap();
+    writeln!(out, "}}").unwrap();
+    out
+}
+
+// Transpiled day 19 and 21 programs, `test_corpus_is_current` fails when
+// the generator output changes and these need to be written again.
+#[cfg(test)]
+#[allow(clippy::all)]
+mod corpus {
+    include!("corpus/day19.rs");
+    include!("corpus/day21.rs");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elfcode::{parse, Machine};
+
+    const DAY19: &str = include_str!("../../inputs/day19.txt");
+    const DAY21: &str = include_str!("../../inputs/day21.txt");
+
+    #[test]
+    fn test_transpile() {
+        let program = parse("#ip 1\nseti 5 0 0\ngtri 0 3 1\nseti 9 9 1", 2).unwrap();
+        let expected = "// Generated by elfcode::transpile, do not edit.
+pub fn run(regs: [usize; 2]) -> [usize; 2] {
+    let [mut r0, _] = regs;
+    let mut ip: usize = 0;
+    loop {
+        match ip {
+            0 => {
+                r0 = 5;
+                ip = 1;
+            }
+            1 => {
+                ip = usize::from(r0 > 3) + 1;
+            }
+            2 => {
+                ip = 10;
+            }
+            _ => break,
+        }
+    }
+    let r1 = ip - 1;
+    [r0, r1]
+}
+";
+        assert_eq!(transpile(&program, "run", 2), expected);
+    }
+
+    #[test]
+    fn test_corpus_is_current() {
+        let day19 = transpile(&parse(DAY19, 6).unwrap(), "day19", 6);
+        assert_eq!(day19, include_str!("corpus/day19.rs"));
+        let day21 = transpile(&parse(DAY21, 6).unwrap(), "day21", 6);
+        assert_eq!(day21, include_str!("corpus/day21.rs"));
+    }
+
+    #[test]
+    fn test_corpus_day19() {
+        let program = parse(DAY19, 6).unwrap();
+        let regs = [0; 6];
+        let mut m = Machine::new(program.ip, regs);
+        m.run(&program.codes);
+        assert_eq!(corpus::day19(regs), m.regs);
+        assert_eq!(corpus::day19([1, 0, 0, 0, 0, 0])[0], 22302144);
+    }
+
+    #[test]
+    fn test_corpus_day21() {
+        // Only halts when r[0] holds one of the values it generates.
+        let program = parse(DAY21, 6).unwrap();
+        let regs = [13522479, 0, 0, 0, 0, 0];
+        let mut m = Machine::new(program.ip, regs);
+        m.run(&program.codes);
+        assert_eq!(corpus::day21(regs), m.regs);
+    }
+}
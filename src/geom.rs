@@ -0,0 +1,133 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+// Cell of a grid, ordered in reading order: top-to-bottom, then left-to-right.
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Copy, Clone, Hash, Default)]
+pub struct Pos {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Pos {
+    pub fn new(row: usize, col: usize) -> Pos {
+        Pos { row, col }
+    }
+
+    // Returns None when the move leaves the non-negative quadrant.
+    pub fn offset(self, drow: isize, dcol: isize) -> Option<Pos> {
+        let row = (self.row as isize).checked_add(drow)?;
+        let col = (self.col as isize).checked_add(dcol)?;
+        if row < 0 || col < 0 {
+            None
+        } else {
+            Some(Pos::new(row as usize, col as usize))
+        }
+    }
+
+    pub fn manhattan(self, that: Pos) -> usize {
+        let dr = (self.row as isize - that.row as isize).unsigned_abs();
+        let dc = (self.col as isize - that.col as isize).unsigned_abs();
+        dr + dc
+    }
+}
+
+impl fmt::Display for Pos {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.row, self.col)
+    }
+}
+
+// Signed 2D vector for unbounded plane coordinates.
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Copy, Clone, Hash, Default)]
+pub struct V2 {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl V2 {
+    pub const ZERO: V2 = V2 { x: 0, y: 0 };
+
+    pub fn new(x: i64, y: i64) -> V2 {
+        V2 { x, y }
+    }
+
+    pub fn manhattan(self) -> u64 {
+        self.x.unsigned_abs() + self.y.unsigned_abs()
+    }
+}
+
+impl fmt::Display for V2 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<{}, {}>", self.x, self.y)
+    }
+}
+
+impl Add for V2 {
+    type Output = V2;
+    fn add(self, that: V2) -> V2 {
+        V2::new(self.x + that.x, self.y + that.y)
+    }
+}
+
+impl Sub for V2 {
+    type Output = V2;
+    fn sub(self, that: V2) -> V2 {
+        V2::new(self.x - that.x, self.y - that.y)
+    }
+}
+
+impl Mul<i64> for V2 {
+    type Output = V2;
+    fn mul(self, k: i64) -> V2 {
+        V2::new(self.x * k, self.y * k)
+    }
+}
+
+impl Neg for V2 {
+    type Output = V2;
+    fn neg(self) -> V2 {
+        V2::new(-self.x, -self.y)
+    }
+}
+
+impl AddAssign for V2 {
+    fn add_assign(&mut self, that: V2) {
+        *self = *self + that;
+    }
+}
+
+impl SubAssign for V2 {
+    fn sub_assign(&mut self, that: V2) {
+        *self = *self - that;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reading_order() {
+        let mut ps = vec![Pos::new(1, 0), Pos::new(0, 2), Pos::new(0, 1)];
+        ps.sort();
+        assert_eq!(ps, vec![Pos::new(0, 1), Pos::new(0, 2), Pos::new(1, 0)]);
+    }
+
+    #[test]
+    fn test_offset() {
+        let p = Pos::new(1, 1);
+        assert_eq!(p.offset(-1, 1), Some(Pos::new(0, 2)));
+        assert_eq!(p.offset(-2, 0), None);
+        assert_eq!(p.manhattan(Pos::new(3, 0)), 3);
+    }
+
+    #[test]
+    fn test_v2() {
+        let a = V2::new(3, -4);
+        let mut b = a * 2 - V2::new(1, 1);
+        assert_eq!(b, V2::new(5, -9));
+        b += -a;
+        assert_eq!(b, V2::new(2, -5));
+        assert_eq!(b.manhattan(), 7);
+    }
+}
@@ -0,0 +1,273 @@
+use std::error::Error;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::geom::Pos;
+
+// Dense row-major 2D grid.
+#[derive(Debug, Eq, PartialEq, Clone, Hash)]
+pub struct Grid<T> {
+    rows: usize,
+    cols: usize,
+    cells: Vec<T>,
+}
+
+// Line `line` (1-based) has `len` cells while the grid has `cols` columns.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct ShapeError {
+    pub line: usize,
+    pub len: usize,
+    pub cols: usize,
+}
+
+impl fmt::Display for ShapeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}: expected {} cells, found {}",
+            self.line, self.cols, self.len
+        )
+    }
+}
+
+impl Error for ShapeError {}
+
+const DIR4: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+const DIR8: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+impl<T: Clone> Grid<T> {
+    pub fn new(rows: usize, cols: usize, fill: T) -> Grid<T> {
+        Grid {
+            rows,
+            cols,
+            cells: vec![fill; rows * cols],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn from_fn<F: FnMut(Pos) -> T>(rows: usize, cols: usize, mut f: F) -> Grid<T> {
+        let mut cells = Vec::with_capacity(rows * cols);
+        for row in 0..rows {
+            for col in 0..cols {
+                cells.push(f(Pos::new(row, col)));
+            }
+        }
+        Grid { rows, cols, cells }
+    }
+
+    // Builds a grid from text lines, all of them must be of the same width.
+    // Trailing blank lines are ignored, and so are CR line terminators.
+    pub fn parse<F: FnMut(char) -> T>(text: &str, mut f: F) -> Result<Grid<T>, ShapeError> {
+        let lines = crate::input::lines(text);
+        let cols = lines.first().map(|l| l.chars().count()).unwrap_or(0);
+        let mut cells = Vec::with_capacity(lines.len() * cols);
+        for (i, line) in lines.iter().enumerate() {
+            let len = line.chars().count();
+            if len != cols {
+                return Err(ShapeError {
+                    line: i + 1,
+                    len,
+                    cols,
+                });
+            }
+            cells.extend(line.chars().map(&mut f));
+        }
+        Ok(Grid {
+            rows: lines.len(),
+            cols,
+            cells,
+        })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    // Cells in row-major order: `pos` is at `index_of(pos)`.
+    pub fn as_slice(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn index_of(&self, pos: Pos) -> usize {
+        pos.row * self.cols + pos.col
+    }
+
+    pub fn pos_at(&self, index: usize) -> Pos {
+        Pos::new(index / self.cols, index % self.cols)
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.row < self.rows && pos.col < self.cols
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        if self.contains(pos) {
+            self.cells.get(pos.row * self.cols + pos.col)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            self.cells.get_mut(pos.row * self.cols + pos.col)
+        } else {
+            None
+        }
+    }
+
+    // Returns false (and drops the value) when `pos` is out of bounds.
+    pub fn set(&mut self, pos: Pos, val: T) -> bool {
+        match self.get_mut(pos) {
+            Some(cell) => {
+                *cell = val;
+                true
+            }
+            None => false,
+        }
+    }
+
+    // All positions in reading order.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let cols = self.cols;
+        (0..self.rows * self.cols).map(move |i| Pos::new(i / cols, i % cols))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn count<F: Fn(&T) -> bool>(&self, f: F) -> usize {
+        self.cells.iter().filter(|c| f(c)).count()
+    }
+
+    // Orthogonal neighbours within bounds, in reading order.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.around(pos, &DIR4)
+    }
+
+    // Orthogonal and diagonal neighbours within bounds, in reading order.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.around(pos, &DIR8)
+    }
+
+    fn around<'a>(
+        &'a self,
+        pos: Pos,
+        dirs: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = Pos> + 'a {
+        dirs.iter()
+            .filter_map(move |&(dr, dc)| pos.offset(dr, dc))
+            .filter(move |p| self.contains(*p))
+    }
+
+    // Renders each row as a line of characters.
+    pub fn render<F: Fn(&T) -> char>(&self, f: F) -> Vec<String> {
+        if self.cols == 0 {
+            return vec![String::new(); self.rows];
+        }
+        self.cells
+            .chunks(self.cols)
+            .map(|row| row.iter().map(&f).collect())
+            .collect()
+    }
+}
+
+impl Grid<char> {
+    pub fn dump(&self) -> Vec<String> {
+        self.render(|c| *c)
+    }
+}
+
+impl fmt::Display for Grid<char> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for line in self.dump() {
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{} is outside of {}x{} grid", pos, self.rows, self.cols))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let (rows, cols) = (self.rows, self.cols);
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{} is outside of {}x{} grid", pos, rows, cols))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "#..\r\n.#.\r\n";
+
+    #[test]
+    fn test_parse() {
+        let grid = Grid::parse(TEXT, |c| c).unwrap();
+        assert_eq!((grid.rows(), grid.cols()), (2, 3));
+        assert_eq!(grid.dump(), vec!["#..", ".#."]);
+        assert_eq!(grid.to_string(), "#..\n.#.\n");
+        assert_eq!(grid.count(|c| *c == '#'), 2);
+        assert_eq!(
+            Grid::parse("##\n#\n", |c| c),
+            Err(ShapeError {
+                line: 2,
+                len: 1,
+                cols: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_get_set() {
+        let mut grid = Grid::new(2, 3, 0);
+        assert!(grid.set(Pos::new(1, 2), 7));
+        assert!(!grid.set(Pos::new(2, 0), 7));
+        assert!(!grid.set(Pos::new(0, 3), 7));
+        assert_eq!(grid.get(Pos::new(1, 2)), Some(&7));
+        assert_eq!(grid.get(Pos::new(0, 3)), None);
+        grid[Pos::new(0, 0)] += 1;
+        assert_eq!(grid.index_of(Pos::new(1, 2)), 5);
+        assert_eq!(grid.pos_at(5), Pos::new(1, 2));
+        assert_eq!(grid.as_slice(), &[1, 0, 0, 0, 0, 7]);
+        assert_eq!(
+            grid.render(|n| (b'0' + *n as u8) as char),
+            vec!["100", "007"]
+        );
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = Grid::from_fn(3, 3, |p| p.row * 3 + p.col);
+        let n4: Vec<usize> = grid.neighbours4(Pos::new(1, 1)).map(|p| grid[p]).collect();
+        assert_eq!(n4, vec![1, 3, 5, 7]);
+        let n8: Vec<usize> = grid.neighbours8(Pos::new(0, 0)).map(|p| grid[p]).collect();
+        assert_eq!(n8, vec![1, 3, 4]);
+        let n4: Vec<usize> = grid.neighbours4(Pos::new(2, 2)).map(|p| grid[p]).collect();
+        assert_eq!(n4, vec![5, 7]);
+    }
+}
@@ -0,0 +1,66 @@
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+
+// Where the puzzle input comes from.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum Source {
+    Stdin,
+    Path(PathBuf),
+    Text(String),
+}
+
+impl Source {
+    pub fn read(&self) -> io::Result<String> {
+        match self {
+            Source::Stdin => {
+                let mut buffer = String::new();
+                io::stdin().lock().read_to_string(&mut buffer)?;
+                Ok(buffer)
+            }
+            Source::Path(path) => fs::read_to_string(path),
+            Source::Text(text) => Ok(text.clone()),
+        }
+    }
+}
+
+impl From<&str> for Source {
+    fn from(text: &str) -> Source {
+        Source::Text(text.to_string())
+    }
+}
+
+// Reads the whole of stdin, panics on I/O errors.
+pub fn stdin() -> String {
+    Source::Stdin.read().unwrap()
+}
+
+// Splits text into lines without line terminators (CRLF aware), dropping
+// trailing blank lines.
+pub fn lines(text: &str) -> Vec<String> {
+    let mut result: Vec<String> = text.lines().map(String::from).collect();
+    while result.last().map(|s| s.trim().is_empty()) == Some(true) {
+        result.pop();
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text() {
+        let src = Source::from("a\r\nb\n\n");
+        let text = src.read().unwrap();
+        assert_eq!(lines(&text), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_path() {
+        let src = Source::Path(PathBuf::from("inputs/day22.txt"));
+        assert!(src.read().unwrap().starts_with("depth: "));
+        let src = Source::Path(PathBuf::from("inputs/nope.txt"));
+        assert!(src.read().is_err());
+    }
+}
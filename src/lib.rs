@@ -1,7 +1,7 @@
-#[cfg(test)]
-mod tests {
-    #[test]
-    fn it_works() {
-        assert_eq!(2 + 2, 4);
-    }
-}
+pub mod days;
+pub mod disjoint;
+pub mod elfcode;
+pub mod geom;
+pub mod grid;
+pub mod input;
+pub mod search;
@@ -0,0 +1,344 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+use crate::geom::Pos;
+use crate::grid::Grid;
+
+// Nodes one step away from `node` are pushed to `out` along with the cost of the step.
+pub trait Graph<N> {
+    fn edges(&mut self, node: N, out: &mut Vec<(N, usize)>);
+}
+
+impl<N, F: FnMut(N, &mut Vec<(N, usize)>)> Graph<N> for F {
+    fn edges(&mut self, node: N, out: &mut Vec<(N, usize)>) {
+        self(node, out)
+    }
+}
+
+// How a node was reached: distance from the closest start and the node before it.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct Visit<N> {
+    pub dist: usize,
+    pub prev: Option<N>,
+}
+
+// Storage of the visits, so that dense graphs don't have to go through hashing.
+pub trait Visits<N> {
+    fn get(&self, node: N) -> Option<Visit<N>>;
+    fn set(&mut self, node: N, visit: Visit<N>);
+}
+
+impl<N: Copy + Eq + Hash> Visits<N> for HashMap<N, Visit<N>> {
+    fn get(&self, node: N) -> Option<Visit<N>> {
+        HashMap::get(self, &node).copied()
+    }
+
+    fn set(&mut self, node: N, visit: Visit<N>) {
+        self.insert(node, visit);
+    }
+}
+
+impl Visits<Pos> for Grid<Option<Visit<Pos>>> {
+    fn get(&self, node: Pos) -> Option<Visit<Pos>> {
+        Grid::get(self, node).copied().flatten()
+    }
+
+    // Nodes outside of the grid are never stored, so the graph must stay within it.
+    fn set(&mut self, node: Pos, visit: Visit<Pos>) {
+        Grid::set(self, node, Some(visit));
+    }
+}
+
+// Outcome of a search: the visits (distance map and predecessor tree),
+// and the goal where the search stopped, if any was reached.
+pub struct Search<N, M> {
+    pub visits: M,
+    pub goal: Option<N>,
+}
+
+impl<N: Copy, M: Visits<N>> Search<N, M> {
+    pub fn dist(&self, node: N) -> Option<usize> {
+        self.visits.get(node).map(|v| v.dist)
+    }
+
+    // Nodes from the start to `node`, both included.
+    pub fn path(&self, node: N) -> Option<Vec<N>> {
+        let mut visit = self.visits.get(node)?;
+        let mut path = vec![node];
+        while let Some(prev) = visit.prev {
+            path.push(prev);
+            visit = self.visits.get(prev)?;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+// Breadth-first search ignoring the costs of the edges: distances are numbers of steps.
+// The first way found to a node is kept: earlier starts and edges win the ties.
+pub fn bfs<N, G, M, F>(graph: &mut G, starts: &[N], mut visits: M, mut goal: F) -> Search<N, M>
+where
+    N: Copy,
+    G: Graph<N>,
+    M: Visits<N>,
+    F: FnMut(N) -> bool,
+{
+    let mut queue = VecDeque::new();
+    for &start in starts {
+        if visits.get(start).is_none() {
+            visits.set(
+                start,
+                Visit {
+                    dist: 0,
+                    prev: None,
+                },
+            );
+            queue.push_back(start);
+        }
+    }
+    let mut edges = Vec::new();
+    while let Some(node) = queue.pop_front() {
+        if goal(node) {
+            return Search {
+                visits,
+                goal: Some(node),
+            };
+        }
+        let dist = visits.get(node).map(|v| v.dist).unwrap_or(0) + 1;
+        edges.clear();
+        graph.edges(node, &mut edges);
+        for &(next, _) in edges.iter() {
+            if visits.get(next).is_none() {
+                visits.set(
+                    next,
+                    Visit {
+                        dist,
+                        prev: Some(node),
+                    },
+                );
+                queue.push_back(next);
+            }
+        }
+    }
+    Search { visits, goal: None }
+}
+
+// Dijkstra's search: nodes are settled by distance, then by `order` (e.g. reading order),
+// the first goal settled is the closest one with the lowest order.
+pub fn dijkstra<N, G, M, F, O, K>(
+    graph: &mut G,
+    starts: &[N],
+    visits: M,
+    goal: F,
+    order: O,
+) -> Search<N, M>
+where
+    N: Copy,
+    G: Graph<N>,
+    M: Visits<N>,
+    F: FnMut(N) -> bool,
+    O: Fn(N) -> K,
+    K: Ord,
+{
+    astar(graph, starts, visits, goal, |_| 0, order)
+}
+
+struct Entry<N, K> {
+    estimate: usize,
+    key: K,
+    dist: usize,
+    node: N,
+}
+
+impl<N, K: Ord> Ord for Entry<N, K> {
+    // Reversed: BinaryHeap pops the greatest entry, the search needs the least one.
+    fn cmp(&self, that: &Self) -> Ordering {
+        (that.estimate, &that.key).cmp(&(self.estimate, &self.key))
+    }
+}
+
+impl<N, K: Ord> PartialOrd for Entry<N, K> {
+    fn partial_cmp(&self, that: &Self) -> Option<Ordering> {
+        Some(self.cmp(that))
+    }
+}
+
+impl<N, K: Ord> PartialEq for Entry<N, K> {
+    fn eq(&self, that: &Self) -> bool {
+        self.cmp(that) == Ordering::Equal
+    }
+}
+
+impl<N, K: Ord> Eq for Entry<N, K> {}
+
+// A* search: `heuristic` must never overestimate the distance to the closest goal
+// (nor drop by more than the cost of an edge), otherwise the goal might be missed.
+// Nodes with the same estimate are settled by `order`, and among equally short ways
+// to a node the one through the node settled first is kept.
+pub fn astar<N, G, M, F, H, O, K>(
+    graph: &mut G,
+    starts: &[N],
+    mut visits: M,
+    mut goal: F,
+    heuristic: H,
+    order: O,
+) -> Search<N, M>
+where
+    N: Copy,
+    G: Graph<N>,
+    M: Visits<N>,
+    F: FnMut(N) -> bool,
+    H: Fn(N) -> usize,
+    O: Fn(N) -> K,
+    K: Ord,
+{
+    let entry = |node: N, dist: usize| Entry {
+        estimate: dist + heuristic(node),
+        key: order(node),
+        dist,
+        node,
+    };
+
+    let mut heap = BinaryHeap::new();
+    for &start in starts {
+        if visits.get(start).is_none() {
+            visits.set(
+                start,
+                Visit {
+                    dist: 0,
+                    prev: None,
+                },
+            );
+            heap.push(entry(start, 0));
+        }
+    }
+    let mut edges = Vec::new();
+    while let Some(Entry { dist, node, .. }) = heap.pop() {
+        if visits.get(node).map(|v| v.dist < dist).unwrap_or(false) {
+            continue; // stale entry, the node was reached sooner afterwards
+        }
+        if goal(node) {
+            return Search {
+                visits,
+                goal: Some(node),
+            };
+        }
+        edges.clear();
+        graph.edges(node, &mut edges);
+        for &(next, cost) in edges.iter() {
+            let dist = dist + cost;
+            if visits.get(next).map(|v| dist < v.dist).unwrap_or(true) {
+                visits.set(
+                    next,
+                    Visit {
+                        dist,
+                        prev: Some(node),
+                    },
+                );
+                heap.push(entry(next, dist));
+            }
+        }
+    }
+    Search { visits, goal: None }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cmp::Reverse;
+
+    // 0 - 1 - 2
+    // |       |     (2 - 5 costs 5, the rest 1)
+    // 3 - 4 - 5 - 6
+    fn edges(node: usize, out: &mut Vec<(usize, usize)>) {
+        let all = [
+            (0, 1, 1),
+            (1, 2, 1),
+            (0, 3, 1),
+            (2, 5, 5),
+            (3, 4, 1),
+            (4, 5, 1),
+            (5, 6, 1),
+        ];
+        for &(a, b, cost) in all.iter() {
+            if a == node {
+                out.push((b, cost));
+            } else if b == node {
+                out.push((a, cost));
+            }
+        }
+    }
+
+    #[test]
+    fn test_bfs() {
+        let search = bfs(&mut edges, &[0], HashMap::new(), |_| false);
+        let dist: Vec<Option<usize>> = (0..8).map(|n| search.dist(n)).collect();
+        assert_eq!(
+            dist,
+            vec![
+                Some(0),
+                Some(1),
+                Some(2),
+                Some(1),
+                Some(2),
+                Some(3),
+                Some(4),
+                None
+            ]
+        );
+        assert_eq!(search.path(6), Some(vec![0, 1, 2, 5, 6]));
+        assert_eq!(search.path(7), None);
+
+        let search = bfs(&mut edges, &[6], HashMap::new(), |n| n < 3);
+        assert_eq!(search.goal, Some(2));
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let search = dijkstra(&mut edges, &[0], HashMap::new(), |_| false, |n| n);
+        assert_eq!(search.dist(6), Some(4));
+        assert_eq!(search.path(6), Some(vec![0, 3, 4, 5, 6]));
+        assert_eq!(search.path(2), Some(vec![0, 1, 2]));
+
+        // Both 1 and 5 are two steps away from 3, the order decides.
+        let search = dijkstra(
+            &mut edges,
+            &[3],
+            HashMap::new(),
+            |n| n == 1 || n == 5,
+            |n| n,
+        );
+        assert_eq!(search.goal, Some(1));
+        let search = dijkstra(
+            &mut edges,
+            &[3],
+            HashMap::new(),
+            |n| n == 1 || n == 5,
+            Reverse,
+        );
+        assert_eq!(search.goal, Some(5));
+    }
+
+    #[test]
+    fn test_astar_grid() {
+        let walls = Grid::parse("....\n.##.\n.#..\n....", |c| c == '#').unwrap();
+        let mut graph = |p: Pos, out: &mut Vec<(Pos, usize)>| {
+            out.extend(walls.neighbours4(p).filter(|q| !walls[*q]).map(|q| (q, 1)));
+        };
+        let goal = Pos::new(2, 2);
+        let search = astar(
+            &mut graph,
+            &[Pos::new(0, 0)],
+            Grid::new(4, 4, None),
+            |p| p == goal,
+            |p| p.manhattan(goal),
+            |p| p,
+        );
+        assert_eq!(search.goal, Some(goal));
+        assert_eq!(search.dist(goal), Some(6));
+        let path = search.path(goal).unwrap();
+        assert_eq!(path.len(), 7);
+        assert_eq!(path[1], Pos::new(0, 1));
+    }
+}